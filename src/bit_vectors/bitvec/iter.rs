@@ -1,50 +1,76 @@
+use std::iter::FusedIterator;
+use std::ops::Range;
+
 use super::BitVec;
+use crate::util::{bitmask, Word};
 
-impl BitVec {
-    pub fn iter(&self) -> Iter {
+impl<W: Word> BitVec<W> {
+    pub fn iter(&self) -> Iter<'_, W> {
         Iter::new(self)
     }
+
+    /// Returns an iterator over the positions of the 1s in the bit vector.
+    pub fn iter_ones(&self) -> IterOnes<'_, W> {
+        IterOnes::new(self, 0..self.len)
+    }
+
+    /// Returns an iterator over the positions of the 0s in the bit vector.
+    pub fn iter_zeros(&self) -> IterZeros<'_, W> {
+        IterZeros::new(self, 0..self.len)
+    }
+
+    /// Returns an iterator over the positions of the 1s in the given range of the bit vector.
+    /// The positions are relative to the start of the bit vector, not to the start of the range.
+    pub fn iter_ones_range(&self, range: Range<usize>) -> IterOnes<'_, W> {
+        IterOnes::new(self, range)
+    }
+
+    /// Returns an iterator over the positions of the 0s in the given range of the bit vector.
+    /// The positions are relative to the start of the bit vector, not to the start of the range.
+    pub fn iter_zeros_range(&self, range: Range<usize>) -> IterZeros<'_, W> {
+        IterZeros::new(self, range)
+    }
 }
 
-impl IntoIterator for BitVec {
+impl<W: Word> IntoIterator for BitVec<W> {
     type Item = bool;
-    type IntoIter = IntoIter;
+    type IntoIter = IntoIter<W>;
 
     fn into_iter(self) -> Self::IntoIter {
         IntoIter::new(self)
     }
 }
 
-impl<'a> IntoIterator for &'a BitVec {
+impl<'a, W: Word> IntoIterator for &'a BitVec<W> {
     type Item = bool;
-    type IntoIter = Iter<'a>;
+    type IntoIter = Iter<'a, W>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
-pub struct IntoIter {
-    bitvec: BitVec,
+pub struct IntoIter<W: Word = usize> {
+    bitvec: BitVec<W>,
     index: usize,
 }
-impl IntoIter {
-    pub fn new(bitvec: BitVec) -> Self {
+impl<W: Word> IntoIter<W> {
+    pub fn new(bitvec: BitVec<W>) -> Self {
         Self { bitvec, index: 0 }
     }
 }
-pub struct Iter<'a> {
-    bitvec: &'a BitVec,
+pub struct Iter<'a, W: Word = usize> {
+    bitvec: &'a BitVec<W>,
     index: usize,
 }
 
-impl<'a> Iter<'a> {
-    pub fn new(bitvec: &'a BitVec) -> Self {
+impl<'a, W: Word> Iter<'a, W> {
+    pub fn new(bitvec: &'a BitVec<W>) -> Self {
         Self { bitvec, index: 0 }
     }
 }
 
-impl Iterator for IntoIter {
+impl<W: Word> Iterator for IntoIter<W> {
     type Item = bool;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -62,7 +88,7 @@ impl Iterator for IntoIter {
     }
 }
 
-impl Iterator for Iter<'_> {
+impl<W: Word> Iterator for Iter<'_, W> {
     type Item = bool;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -79,20 +105,169 @@ impl Iterator for Iter<'_> {
     }
 }
 
+/// Iterator over the positions of the bits with value `VALUE` in a range of a bit vector.
+/// It skips whole words by counting trailing (or leading, when iterating from the back)
+/// zeros, so it is fast even when the bits we look for are sparse.
+pub struct IterBits<'a, W: Word, const VALUE: bool> {
+    raw_data: &'a [W],
+    front_index: usize,
+    // Bits of the front word that have not been yielded yet
+    front_word: W,
+    back_index: usize,
+    // Bits of the back word that have not been yielded yet. When the front and back
+    // words are the same, all the pending bits are stored in `front_word`.
+    back_word: W,
+    remaining: usize,
+}
+
+pub type IterOnes<'a, W = usize> = IterBits<'a, W, true>;
+pub type IterZeros<'a, W = usize> = IterBits<'a, W, false>;
+
+impl<'a, W: Word, const VALUE: bool> IterBits<'a, W, VALUE> {
+    pub fn new(bitvec: &'a BitVec<W>, range: Range<usize>) -> Self {
+        assert!(
+            range.start <= range.end && range.end <= bitvec.len(),
+            "Cannot iterate range {range:?} of a BitVec of length {}",
+            bitvec.len()
+        );
+
+        let raw_data = bitvec.raw_data();
+        if range.is_empty() {
+            return Self {
+                raw_data,
+                front_index: 0,
+                front_word: W::ZERO,
+                back_index: 0,
+                back_word: W::ZERO,
+                remaining: 0,
+            };
+        }
+
+        let front_index = range.start / W::BITS;
+        let back_index = (range.end - 1) / W::BITS;
+
+        // Keep the bits at positions >= start in the first word, and < end in the last word
+        let front_mask = !bitmask!(W, range.start % W::BITS, 0);
+        let back_mask = bitmask!(W, range.end - back_index * W::BITS, 0);
+
+        let (front_word, back_word) = if front_index == back_index {
+            let word = Self::load(raw_data, front_index) & front_mask & back_mask;
+            (word, W::ZERO)
+        } else {
+            (
+                Self::load(raw_data, front_index) & front_mask,
+                Self::load(raw_data, back_index) & back_mask,
+            )
+        };
+
+        let inner_count = (front_index + 1..back_index)
+            .map(|i| Self::load(raw_data, i).count_ones() as usize)
+            .sum::<usize>();
+        let remaining =
+            front_word.count_ones() as usize + inner_count + back_word.count_ones() as usize;
+
+        Self {
+            raw_data,
+            front_index,
+            front_word,
+            back_index,
+            back_word,
+            remaining,
+        }
+    }
+
+    /// Returns the word at index `i`, with the bits we look for set to 1.
+    #[inline]
+    fn load(raw_data: &[W], i: usize) -> W {
+        let word = unsafe { *raw_data.get_unchecked(i) };
+        if VALUE {
+            word
+        } else {
+            !word
+        }
+    }
+}
+
+impl<W: Word, const VALUE: bool> Iterator for IterBits<'_, W, VALUE> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front_word == W::ZERO {
+            if self.front_index >= self.back_index {
+                return None;
+            }
+            self.front_index += 1;
+            self.front_word = if self.front_index == self.back_index {
+                std::mem::take(&mut self.back_word)
+            } else {
+                Self::load(self.raw_data, self.front_index)
+            };
+        }
+
+        let bit_index = self.front_word.trailing_zeros() as usize;
+        self.front_word ^= W::ONE << bit_index;
+        self.remaining -= 1;
+        Some(self.front_index * W::BITS + bit_index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<W: Word, const VALUE: bool> DoubleEndedIterator for IterBits<'_, W, VALUE> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // When both ends reached the same word, the pending bits are in `front_word`
+        let word = loop {
+            if self.front_index == self.back_index {
+                break &mut self.front_word;
+            }
+            if self.back_word != W::ZERO {
+                break &mut self.back_word;
+            }
+            self.back_index -= 1;
+            if self.back_index != self.front_index {
+                self.back_word = Self::load(self.raw_data, self.back_index);
+            }
+        };
+
+        if *word == W::ZERO {
+            return None;
+        }
+
+        let bit_index = W::BITS - 1 - word.leading_zeros() as usize;
+        *word ^= W::ONE << bit_index;
+        self.remaining -= 1;
+        Some(self.back_index * W::BITS + bit_index)
+    }
+}
+
+impl<W: Word, const VALUE: bool> ExactSizeIterator for IterBits<'_, W, VALUE> {}
+
+impl<W: Word, const VALUE: bool> FusedIterator for IterBits<'_, W, VALUE> {}
+
 // Create Bitvec from other iterable types
 
+/// Packs the bools into a word, the first bool being the least significant bit.
+#[inline]
+fn pack_word<W: Word>(bits: impl IntoIterator<Item = bool>) -> W {
+    bits.into_iter()
+        .enumerate()
+        .fold(W::ZERO, |word, (i, bit)| word | (W::from(bit) << i))
+}
+
 impl From<&[bool]> for BitVec {
     fn from(slice: &[bool]) -> Self {
         let mut bitvec = BitVec::with_capacity(slice.len());
-        for bit in slice {
-            bitvec.push(*bit);
+        for chunk in slice.chunks(BitVec::CONTAINER_WIDTH) {
+            bitvec.push_bits(pack_word::<usize>(chunk.iter().copied()), chunk.len());
         }
         bitvec
     }
 }
 
-// TODO: collect bools,u8 and u16 into usize and then push?
-// using slice::chunks method
 impl From<Vec<bool>> for BitVec {
     fn from(vec: Vec<bool>) -> Self {
         vec.as_slice().into()
@@ -105,32 +280,50 @@ impl<const N: usize> From<[bool; N]> for BitVec {
     }
 }
 
-impl FromIterator<bool> for BitVec {
+impl<W: Word> FromIterator<bool> for BitVec<W> {
     fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
-        let mut bitvec = BitVec::new();
-        for bit in iter {
-            bitvec.push(bit);
-        }
+        let mut bitvec = BitVec::default();
+        bitvec.extend(iter);
         bitvec
     }
 }
 
-impl Extend<bool> for BitVec {
+impl<W: Word> Extend<bool> for BitVec<W> {
     fn extend<T: IntoIterator<Item = bool>>(&mut self, iter: T) {
-        for bit in iter {
-            self.push(bit);
+        let mut iter = iter.into_iter();
+        let (lower_bound, _) = iter.size_hint();
+        self.raw_data
+            .reserve((self.len + lower_bound).div_ceil(W::BITS) - self.raw_data.len());
+
+        // Assemble whole words before appending them
+        loop {
+            let mut width = 0;
+            let word = pack_word::<W>(iter.by_ref().take(W::BITS).inspect(|_| width += 1));
+            if width == 0 {
+                break;
+            }
+            self.push_bits(word, width);
         }
     }
 }
 
-impl<'a> Extend<&'a bool> for BitVec {
+impl<'a, W: Word> Extend<&'a bool> for BitVec<W> {
     fn extend<T: IntoIterator<Item = &'a bool>>(&mut self, iter: T) {
-        for bit in iter {
-            self.push(*bit);
-        }
+        self.extend(iter.into_iter().copied());
     }
 }
 
+/// Pushes the bits of an integer of any width, in LSB-first order, into the bit vector.
+/// Integers wider than the container width are pushed in container width chunks.
+macro_rules! push_int {
+    ($bitvec: expr, $value: expr, $t:ty) => {
+        for offset in (0..<$t>::BITS as usize).step_by(BitVec::CONTAINER_WIDTH) {
+            let width = (<$t>::BITS as usize - offset).min(BitVec::CONTAINER_WIDTH);
+            $bitvec.push_bits(($value >> offset) as usize, width);
+        }
+    };
+}
+
 macro_rules! impl_from_for {
     ($($t:ty),*) => {
         $(
@@ -138,7 +331,7 @@ macro_rules! impl_from_for {
                 fn from(slice: &[$t]) -> Self {
                     let mut bitvec = BitVec::with_capacity(std::mem::size_of_val(slice) * 8);
                     for value in slice {
-                        bitvec.push_bits(*value, std::mem::size_of::<$t>() * 8);
+                        push_int!(bitvec, *value, $t);
                     }
                     bitvec
                 }
@@ -165,7 +358,7 @@ macro_rules! impl_from_for {
                 fn from_iter<I: IntoIterator<Item = $t>>(iter: I) -> Self {
                     let mut bitvec = BitVec::new();
                     for value in iter {
-                        bitvec.push_bits(value, std::mem::size_of::<$t>() * 8);
+                        push_int!(bitvec, value, $t);
                     }
                     bitvec
                 }
@@ -174,7 +367,7 @@ macro_rules! impl_from_for {
             impl Extend<$t> for BitVec {
                 fn extend<I: IntoIterator<Item = $t>>(&mut self, iter: I) {
                     for value in iter {
-                        self.push_bits(value, std::mem::size_of::<$t>() * 8);
+                        push_int!(self, value, $t);
                     }
                 }
             }
@@ -182,11 +375,11 @@ macro_rules! impl_from_for {
             impl<'a> Extend<&'a $t> for BitVec {
                 fn extend<I: IntoIterator<Item = &'a $t>>(&mut self, iter: I) {
                     for value in iter {
-                        self.push_bits(*value, std::mem::size_of::<$t>() * 8);
+                        push_int!(self, *value, $t);
                     }
                 }
             }
         )*
     };
 }
-impl_from_for!(u8, u16, usize);
+impl_from_for!(u8, u16, u32, u64, u128, usize);
@@ -1,24 +1,26 @@
 use super::rank_select::{Rank, Select};
 use super::Access;
 use crate::profiling::HeapSize;
-use crate::util::{ceil_div, getbits, setbits};
+use crate::util::{bitmask, ceil_div, getbits, select_in_word, setbits, Word};
 use crate::Build;
 use serde::{Deserialize, Serialize};
+use std::cmp::{max, min};
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Range;
 
 /// Data Structure that represents a bit vector using a compact storage.
-// TODO: Be generic over the underlying data container? This could allow to retrieve
-// ints of size greater than u64 (such as u128 or other integer types that could be in the future)
-
+/// The bits are stored in words of type `W`, which also is the type of the values
+/// read and written with `read_bits`, `push_bits` and `set_bits`.
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
-pub struct BitVec {
+// The Word trait already requires the word to be (de)serializable
+#[serde(bound = "")]
+pub struct BitVec<W: Word = usize> {
     /// The underlying data structure
-    raw_data: Vec<usize>,
+    raw_data: Vec<W>,
     len: usize,
 }
 
-impl Debug for BitVec {
+impl<W: Word> Debug for BitVec<W> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let raw_data_formatted = self
             .raw_data
@@ -34,48 +36,83 @@ impl Debug for BitVec {
 }
 
 impl BitVec {
-    pub const CONTAINER_WIDTH: usize = std::mem::size_of::<usize>() * 8;
+    pub const CONTAINER_WIDTH: usize = usize::BITS as usize;
 
     #[inline]
     pub fn new() -> Self {
-        Self {
-            raw_data: Vec::new(),
-            len: 0,
-        }
+        Self::default()
     }
 
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_bit_capacity(capacity)
+    }
+
+    #[inline]
+    pub fn from_value(value: bool, n: usize) -> Self {
+        Self::filled(value, n)
+    }
+}
+
+// The constructors above are only for the default word, so they can be called without
+// naming it, as HashMap::new is only for the default hasher. These are their
+// counterparts for any word, along with Default.
+impl<W: Word> BitVec<W> {
+    /// Creates an empty bit vector with room for `capacity` bits.
+    #[inline]
+    pub fn with_bit_capacity(capacity: usize) -> Self {
         // ceil division with usize
-        let compact_capacity = ceil_div(capacity, BitVec::CONTAINER_WIDTH);
+        let compact_capacity = ceil_div(capacity, W::BITS);
         Self {
             raw_data: Vec::with_capacity(compact_capacity),
             len: 0,
         }
     }
 
+    /// Creates a bit vector of `n` bits set to `value`.
     #[inline]
-    pub fn from_value(value: bool, n: usize) -> Self {
-        let num_words = ceil_div(n, BitVec::CONTAINER_WIDTH);
+    pub fn filled(value: bool, n: usize) -> Self {
+        let num_words = ceil_div(n, W::BITS);
         let mut raw_data = Vec::with_capacity(num_words);
-        let value = if value { usize::MAX } else { 0 };
+        let value = if value { W::MAX } else { W::ZERO };
         raw_data.resize(num_words, value);
 
-        // clear unused bits
-        let last_word_offset = n % BitVec::CONTAINER_WIDTH;
-        if last_word_offset != 0 {
-            let last_word = raw_data.last_mut().unwrap();
-            *last_word &= usize::MAX >> (BitVec::CONTAINER_WIDTH - last_word_offset);
-        }
+        let mut bitvec = Self { raw_data, len: n };
+        bitvec.clear_unused_bits();
+        bitvec
+    }
 
-        Self { raw_data, len: n }
+    /// Creates a bit vector of length `len` that takes ownership of `words`, without copying them.
+    /// The bits are read in LSB-first order, and the words not needed to store `len`
+    /// bits are dropped, as well as the bits past `len` in the last word.
+    #[inline]
+    pub fn from_words(mut words: Vec<W>, len: usize) -> Self {
+        assert!(
+            len <= words.len() * W::BITS,
+            "Cannot create a BitVec of length {len} from {} words of {} bits",
+            words.len(),
+            W::BITS
+        );
+
+        words.truncate(ceil_div(len, W::BITS));
+        let mut bitvec = Self {
+            raw_data: words,
+            len,
+        };
+        bitvec.clear_unused_bits();
+        bitvec
+    }
+
+    /// Returns the underlying words, without copying them. The bits past `len` in the
+    /// last word are 0s.
+    #[inline]
+    pub fn into_words(self) -> Vec<W> {
+        self.raw_data
     }
 
-    // TODO: create a method to create a BitVec with a bool value
-    // repeated n times, or just use from_iter method? (with collect)
     #[inline]
     pub fn capacity(&self) -> usize {
-        self.raw_data.capacity() * BitVec::CONTAINER_WIDTH
+        self.raw_data.capacity() * W::BITS
     }
 
     #[inline]
@@ -89,35 +126,31 @@ impl BitVec {
     }
 
     #[inline]
-    pub fn raw_data(&self) -> &[usize] {
+    pub fn raw_data(&self) -> &[W] {
         &self.raw_data
     }
 
     #[inline]
     pub fn push(&mut self, value: bool) {
-        let (block_index, offset) = (
-            self.len / BitVec::CONTAINER_WIDTH,
-            self.len % BitVec::CONTAINER_WIDTH,
-        );
+        let (block_index, offset) = (self.len / W::BITS, self.len % W::BITS);
 
-        let value = value.into();
+        let value = W::from(value);
 
         if offset == 0 {
             self.raw_data.push(value);
         } else {
-            setbits!(self.raw_data[block_index], 1, offset, value);
+            setbits!(W, self.raw_data[block_index], 1, offset, value);
         }
         self.len += 1;
     }
 
     /// Pushes a value into the bit vector, using the specified width.
     /// The bits will be read in a LSB-first order (i.e from right to left).
-    // TODO: hardcode T as usize? then some asserts can be removed
     // TODO: panic or return result explaining the error? Maybe results would decrease performance
     #[inline]
     pub fn push_bits<T>(&mut self, value: T, width: usize)
     where
-        T: Into<usize>,
+        T: Into<W>,
     {
         // Allow to push a value of size less than the width, the bits will
         // be padded with 0s
@@ -125,19 +158,16 @@ impl BitVec {
 
         // This check prevents from a single value span more than two words
         assert!(
-            width <= BitVec::CONTAINER_WIDTH,
+            width <= W::BITS,
             "Width {width} is greater than the BitVec's container width ({})",
-            BitVec::CONTAINER_WIDTH
+            W::BITS
         );
 
         // TODO: return or crash?
         if width == 0 {
             return;
         }
-        let (block_index, offset) = (
-            self.len / BitVec::CONTAINER_WIDTH,
-            self.len % BitVec::CONTAINER_WIDTH,
-        );
+        let (block_index, offset) = (self.len / W::BITS, self.len % W::BITS);
 
         if offset == 0 {
             self.raw_data.push(value);
@@ -147,7 +177,7 @@ impl BitVec {
             let new_value = shifted_value | last_block;
             self.raw_data[block_index] = new_value;
 
-            let filled_bits = BitVec::CONTAINER_WIDTH - offset;
+            let filled_bits = W::BITS - offset;
 
             if filled_bits < width {
                 let remaining_value = value >> filled_bits;
@@ -167,7 +197,7 @@ impl BitVec {
         self.set(self.len - 1, false);
         self.len -= 1;
 
-        if self.len % BitVec::CONTAINER_WIDTH == 0 {
+        if self.len.is_multiple_of(W::BITS) {
             // If that bit was the last one in the last word, remove that word
             self.raw_data.pop();
         }
@@ -175,7 +205,7 @@ impl BitVec {
     }
 
     #[inline]
-    pub fn pop_bits(&mut self, n: usize) -> usize {
+    pub fn pop_bits(&mut self, n: usize) -> W {
         assert!(
             n <= self.len,
             "Cannot pop {n} bits from a BitVec of length {}",
@@ -185,21 +215,15 @@ impl BitVec {
         let new_len = self.len - n;
         let value = self.read_bits(new_len, n);
 
-        let old_compact_len = ceil_div(self.len, BitVec::CONTAINER_WIDTH);
-        let new_compact_len = ceil_div(new_len, BitVec::CONTAINER_WIDTH);
+        let old_compact_len = ceil_div(self.len, W::BITS);
+        let new_compact_len = ceil_div(new_len, W::BITS);
 
         if old_compact_len > new_compact_len {
             self.raw_data.truncate(new_compact_len);
         }
 
-        // clear the bits of the last word that are not used
-        let last_word_offset = new_len % BitVec::CONTAINER_WIDTH;
-        if last_word_offset != 0 {
-            let last_word = self.raw_data.last_mut().unwrap();
-            *last_word &= usize::MAX >> (BitVec::CONTAINER_WIDTH - last_word_offset);
-        }
-
         self.len = new_len;
+        self.clear_unused_bits();
 
         value
     }
@@ -212,28 +236,25 @@ impl BitVec {
             self.len
         );
 
-        let (block_index, offset) = (
-            index / BitVec::CONTAINER_WIDTH,
-            index % BitVec::CONTAINER_WIDTH,
-        );
+        let (block_index, offset) = (index / W::BITS, index % W::BITS);
 
         // TODO: use get_unchecked to avoid bounds check, since
         // we are already checking bounds.
 
         // Optimize this as we know we are reading 1 bit
         // getbits!(self.raw_data[block_index], 1, offset) == 1
-        self.raw_data[block_index] >> offset & 0b1 == 1
+        self.raw_data[block_index] >> offset & W::ONE == W::ONE
     }
 
     #[inline]
-    pub fn read_bits(&self, index: usize, len: usize) -> usize {
+    pub fn read_bits(&self, index: usize, len: usize) -> W {
         if len == 0 {
-            return 0;
+            return W::ZERO;
         }
         assert!(
-            len <= BitVec::CONTAINER_WIDTH,
+            len <= W::BITS,
             "requested len ({len}) is greater than the BitVec's container width ({})",
-            BitVec::CONTAINER_WIDTH
+            W::BITS
         );
 
         assert!(
@@ -249,22 +270,22 @@ impl BitVec {
     /// # Safety
     /// Calling this method with an out-of-bounds `index + len` is undefined behavior.
     #[inline]
-    pub unsafe fn read_bits_unchecked(&self, index: usize, len: usize) -> usize {
+    pub unsafe fn read_bits_unchecked(&self, index: usize, len: usize) -> W {
         // TODO: optimize this method to achieve better performance
         if len == 0 {
-            return 0;
+            return W::ZERO;
         }
-        let offset = index % BitVec::CONTAINER_WIDTH;
-        let index = index / BitVec::CONTAINER_WIDTH;
+        let offset = index % W::BITS;
+        let index = index / W::BITS;
 
-        let w1 = self.raw_data.get_unchecked(index) >> offset;
-        if offset + len > BitVec::CONTAINER_WIDTH {
-            let w2 = self.raw_data.get_unchecked(index + 1);
-            let read_bits = BitVec::CONTAINER_WIDTH - offset;
+        let w1 = *self.raw_data.get_unchecked(index) >> offset;
+        if offset + len > W::BITS {
+            let w2 = *self.raw_data.get_unchecked(index + 1);
+            let read_bits = W::BITS - offset;
             let rem_bits = len - read_bits;
-            w1 | ((w2 & (usize::MAX >> (BitVec::CONTAINER_WIDTH - rem_bits))) << read_bits)
+            w1 | ((w2 & (W::MAX >> (W::BITS - rem_bits))) << read_bits)
         } else {
-            w1 & (usize::MAX >> (BitVec::CONTAINER_WIDTH - len))
+            w1 & (W::MAX >> (W::BITS - len))
         }
     }
 
@@ -276,19 +297,16 @@ impl BitVec {
             self.len
         );
 
-        let (block_index, offset) = (
-            index / BitVec::CONTAINER_WIDTH,
-            index % BitVec::CONTAINER_WIDTH,
-        );
-        let value: usize = value.into();
+        let (block_index, offset) = (index / W::BITS, index % W::BITS);
+        let value = W::from(value);
 
-        setbits!(self.raw_data[block_index], 1, offset, value);
+        setbits!(W, self.raw_data[block_index], 1, offset, value);
     }
 
     #[inline]
     pub fn set_bits<T>(&mut self, range: Range<usize>, value: T)
     where
-        T: Into<usize>,
+        T: Into<W>,
     {
         if range.start == range.end {
             return;
@@ -308,9 +326,9 @@ impl BitVec {
         );
 
         assert!(
-            width <= BitVec::CONTAINER_WIDTH,
+            width <= W::BITS,
             "Range's width {width} is greater than the BitVec's container width ({})",
-            BitVec::CONTAINER_WIDTH
+            W::BITS
         );
 
         assert!(
@@ -322,18 +340,19 @@ impl BitVec {
             return;
         }
 
-        let first_index = start / BitVec::CONTAINER_WIDTH;
-        let first_offset = start % BitVec::CONTAINER_WIDTH;
+        let first_index = start / W::BITS;
+        let first_offset = start % W::BITS;
 
-        let last_index = end / BitVec::CONTAINER_WIDTH;
+        let last_index = end / W::BITS;
         if first_index == last_index {
             // when the int is contained in a single word
-            setbits!(self.raw_data[first_index], width, first_offset, value);
+            setbits!(W, self.raw_data[first_index], width, first_offset, value);
         } else {
             //when the int is split between two words
-            let first_word_num_bits = BitVec::CONTAINER_WIDTH - first_offset;
-            let first_value = getbits!(value, first_word_num_bits, 0);
+            let first_word_num_bits = W::BITS - first_offset;
+            let first_value = getbits!(W, value, first_word_num_bits, 0);
             setbits!(
+                W,
                 self.raw_data[first_index],
                 first_word_num_bits,
                 first_offset,
@@ -341,27 +360,234 @@ impl BitVec {
             );
 
             let last_word_num_bits = width - first_word_num_bits;
-            let last_value = getbits!(value, last_word_num_bits, first_word_num_bits);
-            setbits!(self.raw_data[last_index], last_word_num_bits, 0, last_value);
+            let last_value = getbits!(W, value, last_word_num_bits, first_word_num_bits);
+            setbits!(
+                W,
+                self.raw_data[last_index],
+                last_word_num_bits,
+                0,
+                last_value
+            );
+        }
+    }
+
+    /// Inserts a bit at position `index`, shifting all the bits after it to the right.
+    #[inline]
+    pub fn insert(&mut self, index: usize, value: bool) {
+        self.insert_bits(index, value, 1);
+    }
+
+    /// Inserts `width` bits of `value` at position `index`, shifting all the bits after it to the right.
+    /// The bits will be read in a LSB-first order (i.e from right to left), as in `push_bits`.
+    #[inline]
+    pub fn insert_bits<T>(&mut self, index: usize, value: T, width: usize)
+    where
+        T: Into<W>,
+    {
+        assert!(
+            index <= self.len,
+            "Cannot insert bits at index {index} in a BitVec of length {}",
+            self.len
+        );
+        assert!(
+            width <= W::BITS,
+            "Width {width} is greater than the BitVec's container width ({})",
+            W::BITS
+        );
+
+        if width == 0 {
+            return;
         }
+
+        self.open_gap(index, width);
+        self.set_bits(index..index + width, value.into());
+    }
+
+    /// Removes the bit at position `index` and returns it, shifting all the bits after it to the left.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> bool {
+        assert!(
+            index < self.len,
+            "Cannot remove bit at index {index} from a BitVec of length {}",
+            self.len
+        );
+
+        let value = self.read(index);
+        self.close_gap(index, 1);
+        value
     }
 
-    // TODO: create a remove function similar to Vec's api
+    /// Removes the bits in `range` and returns them in a new bit vector,
+    /// shifting all the bits after the range to the left.
+    #[inline]
+    pub fn drain(&mut self, range: Range<usize>) -> BitVec<W> {
+        let removed = self.slice(range.clone()).to_bitvec();
+        self.close_gap(range.start, range.end - range.start);
+        removed
+    }
+
+    /// Replaces the bits in `range` with the bits of `replace_with`, and returns the
+    /// removed bits in a new bit vector. The range and the replacement may have different lengths.
+    #[inline]
+    pub fn splice(&mut self, range: Range<usize>, replace_with: &BitVec<W>) -> BitVec<W> {
+        let removed = self.slice(range.clone()).to_bitvec();
+
+        let range_len = range.end - range.start;
+        if replace_with.len() > range_len {
+            self.open_gap(range.end, replace_with.len() - range_len);
+        } else {
+            self.close_gap(
+                range.start + replace_with.len(),
+                range_len - replace_with.len(),
+            );
+        }
+
+        for i in (0..replace_with.len()).step_by(W::BITS) {
+            let width = min(W::BITS, replace_with.len() - i);
+            let start = range.start + i;
+            self.set_bits(start..start + width, replace_with.read_bits(i, width));
+        }
+
+        removed
+    }
+
+    /// Splits the bit vector into two at the given index. Returns a new bit vector
+    /// containing the bits in the range [at, len), and `self` keeps the bits in [0, at).
+    #[inline]
+    pub fn split_off(&mut self, at: usize) -> BitVec<W> {
+        assert!(
+            at <= self.len,
+            "Cannot split off at index {at} a BitVec of length {}",
+            self.len
+        );
+
+        let tail = self.slice(at..self.len).to_bitvec();
+        self.truncate(at);
+        tail
+    }
+
+    /// Moves all the bits of `other` to the end of `self`, leaving `other` empty.
+    #[inline]
+    pub fn append(&mut self, other: &mut BitVec<W>) {
+        self.raw_data
+            .reserve(ceil_div(self.len + other.len, W::BITS) - self.raw_data.len());
+
+        let other_len = other.len;
+        for (i, word) in other.raw_data.iter().enumerate() {
+            let width = min(W::BITS, other_len - i * W::BITS);
+            self.push_bits(*word, width);
+        }
+        other.clear();
+    }
+
+    /// Shortens the bit vector, keeping the first `len` bits. If `len` is greater or equal
+    /// than the current length, this has no effect.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        self.raw_data.truncate(ceil_div(len, W::BITS));
+        self.len = len;
+        self.clear_unused_bits();
+    }
+
+    /// Removes all the bits of the bit vector, keeping its capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Shifts the bits in [index, len) `n` positions to the right, a word at a time,
+    /// increasing the length by `n`. The bits in the gap [index, index + n) are left unspecified.
+    #[inline]
+    fn open_gap(&mut self, index: usize, n: usize) {
+        let new_len = self.len + n;
+        self.raw_data.resize(ceil_div(new_len, W::BITS), W::ZERO);
+        self.len = new_len;
+
+        // Traverse the words backwards, so the bits we read have not been overwritten yet
+        let first_word = index / W::BITS;
+        for j in (first_word..self.raw_data.len()).rev() {
+            let word_start = j * W::BITS;
+            let start = max(word_start, index + n);
+            let end = min(word_start + W::BITS, new_len);
+            if start >= end {
+                // The whole word is part of the gap
+                continue;
+            }
+
+            let bits = unsafe { self.read_bits_unchecked(start - n, end - start) };
+            let local_offset = start - word_start;
+            let word = &mut self.raw_data[j];
+            *word = (*word & bitmask!(W, local_offset, 0)) | (bits << local_offset);
+        }
+    }
+
+    /// Removes the bits in [index, index + n) shifting the following bits `n` positions
+    /// to the left, a word at a time.
+    #[inline]
+    fn close_gap(&mut self, index: usize, n: usize) {
+        assert!(
+            index + n <= self.len,
+            "Cannot remove bits from index {index} to {} from a BitVec of length {}",
+            index + n,
+            self.len
+        );
+
+        if n == 0 {
+            return;
+        }
+
+        let new_len = self.len - n;
+        let new_num_words = ceil_div(new_len, W::BITS);
+
+        // Traverse the words forwards, so the bits we read have not been overwritten yet
+        let first_word = index / W::BITS;
+        for j in first_word..new_num_words {
+            let word_start = j * W::BITS;
+            let start = max(word_start, index);
+            let end = min(word_start + W::BITS, new_len);
+
+            let bits = unsafe { self.read_bits_unchecked(start + n, end - start) };
+            let local_offset = start - word_start;
+            let word = &mut self.raw_data[j];
+            *word = (*word & bitmask!(W, local_offset, 0)) | (bits << local_offset);
+        }
+
+        self.raw_data.truncate(new_num_words);
+        self.len = new_len;
+        self.clear_unused_bits();
+    }
+
+    /// Clears the bits of the last word that are past `len`, so there are no dirty bits.
+    #[inline]
+    fn clear_unused_bits(&mut self) {
+        let last_word_offset = self.len % W::BITS;
+        if last_word_offset != 0 {
+            let last_word = self.raw_data.last_mut().unwrap();
+            *last_word &= W::MAX >> (W::BITS - last_word_offset);
+        }
+    }
 }
 
-impl Default for BitVec {
+impl<W: Word> Default for BitVec<W> {
     fn default() -> Self {
-        Self::new()
+        Self {
+            raw_data: Vec::new(),
+            len: 0,
+        }
     }
 }
 
-impl HeapSize for BitVec {
+impl<W: Word> HeapSize for BitVec<W> {
     fn heap_size_in_bits(&self) -> usize {
         self.raw_data.heap_size_in_bits()
     }
 }
 
-impl Access for BitVec {
+impl<W: Word> Access for BitVec<W> {
     fn access(&self, index: usize) -> Option<bool> {
         if index >= self.len {
             return None;
@@ -370,17 +596,14 @@ impl Access for BitVec {
     }
 }
 
-impl Rank for BitVec {
+impl<W: Word> Rank for BitVec<W> {
     fn rank(&self, index: usize) -> Option<usize> {
         // rank is the number of 1s before the index (range [0, index))
         if index > self.len {
             return None;
         }
 
-        let (block_index, block_offset) = (
-            index / BitVec::CONTAINER_WIDTH,
-            index % BitVec::CONTAINER_WIDTH,
-        );
+        let (block_index, block_offset) = (index / W::BITS, index % W::BITS);
 
         let prev_blocks = &self.raw_data[..block_index];
         let prev_rank = prev_blocks
@@ -388,16 +611,15 @@ impl Rank for BitVec {
             .map(|block| block.count_ones())
             .sum::<u32>() as usize;
 
-        let last_block = self.raw_data.get(block_index).copied().unwrap_or(0);
-        // getbits!(last_block, block_offset, 0) optimized, we know that block_offset is always <64 so this does not overflow
-        let last_block_target = last_block & ((1 << block_offset) - 1);
+        let last_block = self.raw_data.get(block_index).copied().unwrap_or(W::ZERO);
+        let last_block_target = last_block & bitmask!(W, block_offset, 0);
         let last_block_rank = last_block_target.count_ones() as usize;
 
         Some(prev_rank + last_block_rank)
     }
 }
 
-impl Select for BitVec {
+impl<W: Word> Select for BitVec<W> {
     fn select(&self, rank: usize) -> Option<usize> {
         if rank == 0 {
             return Some(0);
@@ -408,17 +630,8 @@ impl Select for BitVec {
             let block_rank = block.count_ones() as usize;
             if rank_count + block_rank >= rank {
                 // Select inside word
-                let mut bit_index = 0;
-                let mut word = *block;
-                while rank_count < rank {
-                    let bit = word & 0b1;
-                    if bit == 1 {
-                        rank_count += 1;
-                    }
-                    word >>= 1;
-                    bit_index += 1;
-                }
-                return Some(i * BitVec::CONTAINER_WIDTH + bit_index);
+                let bit_index = select_in_word(*block, rank - rank_count - 1) + 1;
+                return Some(i * W::BITS + bit_index);
             }
             rank_count += block_rank;
         }
@@ -435,24 +648,15 @@ impl Select for BitVec {
             let block_rank0 = if i == self.raw_data.len() - 1 {
                 // At the last word, it may not be fully completed and there may be dirty bits
                 // at value 0, so we do not count them
-                (self.len - i * BitVec::CONTAINER_WIDTH) - block.count_ones() as usize
+                (self.len - i * W::BITS) - block.count_ones() as usize
             } else {
                 block.count_zeros() as usize
             };
 
             if rank0_count + block_rank0 >= rank0 {
                 // Select inside word
-                let mut bit_index = 0;
-                let mut word = *block;
-                while rank0_count < rank0 {
-                    let bit = word & 0b1;
-                    if bit == 0 {
-                        rank0_count += 1;
-                    }
-                    word >>= 1;
-                    bit_index += 1;
-                }
-                return Some(i * BitVec::CONTAINER_WIDTH + bit_index);
+                let bit_index = select_in_word(!*block, rank0 - rank0_count - 1) + 1;
+                return Some(i * W::BITS + bit_index);
             }
             rank0_count += block_rank0;
         }
@@ -461,7 +665,7 @@ impl Select for BitVec {
     }
 }
 
-impl Display for BitVec {
+impl<W: Word> Display for BitVec<W> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut iter = self.iter();
         write!(f, "[")?;
@@ -497,12 +701,19 @@ impl BitVec {
     }
 }
 
-impl Build<BitVec, BitVec> for BitVecSpec {
-    fn build(&self, data: BitVec) -> BitVec {
+impl<W: Word> Build<BitVec<W>, BitVec<W>> for BitVecSpec {
+    fn build(&self, data: BitVec<W>) -> BitVec<W> {
         data
     }
 }
 
 pub mod iter;
+mod ops;
+mod search;
+pub mod slice;
+mod text;
+mod transform;
+pub use slice::BitSlice;
+pub use text::ParseBitVecError;
 #[cfg(test)]
 mod tests;
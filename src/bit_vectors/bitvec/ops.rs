@@ -0,0 +1,127 @@
+//! Word-parallel bitwise operators between bit vectors.
+//!
+//! When the operands have different lengths, the shorter one is treated as if it
+//! were padded with 0s up to the length of the longer one, so the result always
+//! has the length of the longest operand.
+
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use super::BitVec;
+use crate::util::{ceil_div, Word};
+
+impl<W: Word> BitVec<W> {
+    /// Returns a new bit vector with the bits set in `self` and unset in `other`,
+    /// i.e `self & !other`. The result has the length of the longest operand.
+    #[inline]
+    pub fn and_not(&self, other: &BitVec<W>) -> BitVec<W> {
+        let mut result = self.clone();
+        result.and_not_assign(other);
+        result
+    }
+
+    /// In-place version of [`BitVec::and_not`].
+    #[inline]
+    pub fn and_not_assign(&mut self, other: &BitVec<W>) {
+        self.bitwise_assign(other, |a, b| a & !b);
+    }
+
+    /// Applies `op` word by word between `self` and `other`, storing the result in `self`.
+    /// Missing words of the shorter operand are read as 0s.
+    #[inline]
+    fn bitwise_assign<F>(&mut self, other: &BitVec<W>, op: F)
+    where
+        F: Fn(W, W) -> W,
+    {
+        if other.len > self.len {
+            // There are no dirty bits past len, so the new bits are 0s
+            self.raw_data.resize(ceil_div(other.len, W::BITS), W::ZERO);
+            self.len = other.len;
+        }
+
+        for (i, word) in self.raw_data.iter_mut().enumerate() {
+            let other_word = other.raw_data.get(i).copied().unwrap_or(W::ZERO);
+            *word = op(*word, other_word);
+        }
+
+        self.clear_unused_bits();
+    }
+}
+
+impl<W: Word> Not for BitVec<W> {
+    type Output = BitVec<W>;
+
+    #[inline]
+    fn not(mut self) -> Self::Output {
+        for word in self.raw_data.iter_mut() {
+            *word = !*word;
+        }
+        self.clear_unused_bits();
+        self
+    }
+}
+
+impl<W: Word> Not for &BitVec<W> {
+    type Output = BitVec<W>;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        !self.clone()
+    }
+}
+
+macro_rules! impl_bitwise_op_for {
+    ($(($op_trait:ident, $op_fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt)),*) => {
+        $(
+            impl<W: Word> $assign_trait<&BitVec<W>> for BitVec<W> {
+                #[inline]
+                fn $assign_fn(&mut self, other: &BitVec<W>) {
+                    self.bitwise_assign(other, |a, b| a $op b);
+                }
+            }
+
+            impl<W: Word> $assign_trait<BitVec<W>> for BitVec<W> {
+                #[inline]
+                fn $assign_fn(&mut self, other: BitVec<W>) {
+                    self.$assign_fn(&other);
+                }
+            }
+
+            impl<W: Word> $op_trait<&BitVec<W>> for &BitVec<W> {
+                type Output = BitVec<W>;
+
+                #[inline]
+                fn $op_fn(self, other: &BitVec<W>) -> Self::Output {
+                    let mut result = self.clone();
+                    result.$assign_fn(other);
+                    result
+                }
+            }
+
+            impl<W: Word> $op_trait<&BitVec<W>> for BitVec<W> {
+                type Output = BitVec<W>;
+
+                #[inline]
+                fn $op_fn(mut self, other: &BitVec<W>) -> Self::Output {
+                    self.$assign_fn(other);
+                    self
+                }
+            }
+
+            impl<W: Word> $op_trait<BitVec<W>> for BitVec<W> {
+                type Output = BitVec<W>;
+
+                #[inline]
+                fn $op_fn(mut self, other: BitVec<W>) -> Self::Output {
+                    self.$assign_fn(&other);
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_bitwise_op_for!(
+    (BitAnd, bitand, BitAndAssign, bitand_assign, &),
+    (BitOr, bitor, BitOrAssign, bitor_assign, |),
+    (BitXor, bitxor, BitXorAssign, bitxor_assign, ^)
+);
@@ -0,0 +1,94 @@
+//! Population counts and searches of 1s restricted to a range of the bit vector.
+//! Only the words that overlap with the range are scanned, so they do not need a rank
+//! structure to be efficient on small ranges.
+
+use std::cmp::{max, min};
+use std::ops::Range;
+
+use super::BitVec;
+use crate::util::{bitmask, Word};
+
+impl<W: Word> BitVec<W> {
+    /// Returns the number of 1s in `range`.
+    #[inline]
+    pub fn count_ones(&self, range: Range<usize>) -> usize {
+        self.validate_range(&range);
+        self.word_indexes(&range)
+            .map(|i| unsafe { self.masked_word_unchecked(i, &range) }.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the number of 0s in `range`.
+    #[inline]
+    pub fn count_zeros(&self, range: Range<usize>) -> usize {
+        let len = range.end.saturating_sub(range.start);
+        len - self.count_ones(range)
+    }
+
+    /// Returns the position of the first 1 in `range`, or None if there are no 1s in it.
+    #[inline]
+    pub fn first_one(&self, range: Range<usize>) -> Option<usize> {
+        self.validate_range(&range);
+        self.word_indexes(&range).find_map(|i| {
+            let word = unsafe { self.masked_word_unchecked(i, &range) };
+            (word != W::ZERO).then(|| i * W::BITS + word.trailing_zeros() as usize)
+        })
+    }
+
+    /// Returns the position of the last 1 in `range`, or None if there are no 1s in it.
+    #[inline]
+    pub fn last_one(&self, range: Range<usize>) -> Option<usize> {
+        self.validate_range(&range);
+        self.word_indexes(&range).rev().find_map(|i| {
+            let word = unsafe { self.masked_word_unchecked(i, &range) };
+            (word != W::ZERO).then(|| (i + 1) * W::BITS - 1 - word.leading_zeros() as usize)
+        })
+    }
+
+    /// Returns the position of the first 1 at a position greater or equal than `from`,
+    /// or None if there is none.
+    #[inline]
+    pub fn next_one(&self, from: usize) -> Option<usize> {
+        if from >= self.len {
+            return None;
+        }
+        self.first_one(from..self.len)
+    }
+
+    /// Returns the position of the last 1 at a position less or equal than `from`,
+    /// or None if there is none. If `from` is out of bounds, the whole bit vector is searched.
+    #[inline]
+    pub fn prev_one(&self, from: usize) -> Option<usize> {
+        self.last_one(0..min(from.saturating_add(1), self.len))
+    }
+
+    #[inline]
+    fn validate_range(&self, range: &Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "Range {range:?} is out of bounds of a BitVec of length {}",
+            self.len
+        );
+    }
+
+    /// Indexes of the words that overlap with `range`.
+    #[inline]
+    fn word_indexes(&self, range: &Range<usize>) -> Range<usize> {
+        if range.is_empty() {
+            return 0..0;
+        }
+        range.start / W::BITS..(range.end - 1) / W::BITS + 1
+    }
+
+    /// Returns the word at index `i`, with the bits outside of `range` set to 0.
+    ///
+    /// # Safety
+    /// `i` must be the index of a word that overlaps with `range`.
+    #[inline]
+    unsafe fn masked_word_unchecked(&self, i: usize, range: &Range<usize>) -> W {
+        let word_start = i * W::BITS;
+        let start = max(range.start, word_start) - word_start;
+        let end = min(range.end, word_start + W::BITS) - word_start;
+        *self.raw_data.get_unchecked(i) & bitmask!(W, end - start, start)
+    }
+}
@@ -0,0 +1,323 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Range;
+
+use super::BitVec;
+use crate::bit_vectors::rank_select::{Rank, Select};
+use crate::bit_vectors::Access;
+use crate::util::{ceil_div, select_in_word, Word};
+
+/// Borrowed view over a range of bits of a [`BitVec`], starting at any bit offset.
+/// Creating a view or sub-slicing it does not copy any data.
+#[derive(Clone, Copy)]
+pub struct BitSlice<'a, W: Word = usize> {
+    /// Words that contain the bits of the view. The first bit of the view
+    /// is at bit `offset` of the first word.
+    raw_data: &'a [W],
+    offset: usize,
+    len: usize,
+}
+
+impl<'a, W: Word> BitSlice<'a, W> {
+    /// # Safety
+    /// `raw_data` must contain at least `offset + len` bits.
+    #[inline]
+    unsafe fn from_raw_parts(raw_data: &'a [W], offset: usize, len: usize) -> Self {
+        let first_word = offset / W::BITS;
+        let last_word = ceil_div(offset + len, W::BITS);
+        Self {
+            raw_data: raw_data.get_unchecked(first_word..last_word),
+            offset: offset % W::BITS,
+            len,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a view over the `range` of this view, without copying.
+    #[inline]
+    pub fn slice(&self, range: Range<usize>) -> BitSlice<'a, W> {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "Cannot slice range {range:?} from a BitSlice of length {}",
+            self.len
+        );
+        unsafe {
+            Self::from_raw_parts(
+                self.raw_data,
+                self.offset + range.start,
+                range.end - range.start,
+            )
+        }
+    }
+
+    #[inline]
+    pub fn read(&self, index: usize) -> bool {
+        assert!(
+            index < self.len,
+            "Cannot read bit at index {index} from a BitSlice of length {}",
+            self.len
+        );
+
+        let index = self.offset + index;
+        let (block_index, offset) = (index / W::BITS, index % W::BITS);
+
+        unsafe { *self.raw_data.get_unchecked(block_index) >> offset & W::ONE == W::ONE }
+    }
+
+    #[inline]
+    pub fn read_bits(&self, index: usize, len: usize) -> W {
+        if len == 0 {
+            return W::ZERO;
+        }
+        assert!(
+            len <= W::BITS,
+            "requested len ({len}) is greater than the BitSlice's container width ({})",
+            W::BITS
+        );
+
+        assert!(
+            index + len - 1 < self.len(),
+            "index out of bounds: the len is {}, but the index is {} and the width is {}",
+            self.len(),
+            index,
+            len
+        );
+        unsafe { self.read_bits_unchecked(index, len) }
+    }
+
+    /// # Safety
+    /// Calling this method with an out-of-bounds `index + len` is undefined behavior.
+    #[inline]
+    pub unsafe fn read_bits_unchecked(&self, index: usize, len: usize) -> W {
+        if len == 0 {
+            return W::ZERO;
+        }
+        let index = self.offset + index;
+        let offset = index % W::BITS;
+        let index = index / W::BITS;
+
+        let w1 = *self.raw_data.get_unchecked(index) >> offset;
+        if offset + len > W::BITS {
+            let w2 = *self.raw_data.get_unchecked(index + 1);
+            let read_bits = W::BITS - offset;
+            let rem_bits = len - read_bits;
+            w1 | ((w2 & (W::MAX >> (W::BITS - rem_bits))) << read_bits)
+        } else {
+            w1 & (W::MAX >> (W::BITS - len))
+        }
+    }
+
+    /// Number of words needed to store the bits of the view, as if it started at offset 0.
+    #[inline]
+    fn num_words(&self) -> usize {
+        ceil_div(self.len, W::BITS)
+    }
+
+    /// Returns the `i`-th word of the view, as if it started at offset 0.
+    /// Bits past the end of the view are returned as 0s.
+    ///
+    /// # Safety
+    /// `i` must be less than `self.num_words()`.
+    #[inline]
+    unsafe fn word_unchecked(&self, i: usize) -> W {
+        let start = i * W::BITS;
+        let width = W::BITS.min(self.len - start);
+        self.read_bits_unchecked(start, width)
+    }
+
+    /// Copies the bits of the view into a new [`BitVec`].
+    #[inline]
+    pub fn to_bitvec(&self) -> BitVec<W> {
+        let mut bitvec = BitVec::with_bit_capacity(self.len);
+        for i in 0..self.num_words() {
+            let start = i * W::BITS;
+            let width = W::BITS.min(self.len - start);
+            bitvec.push_bits(unsafe { self.word_unchecked(i) }, width);
+        }
+        bitvec
+    }
+
+    pub fn iter(&self) -> Iter<'a, W> {
+        Iter::new(*self)
+    }
+}
+
+impl<W: Word> BitVec<W> {
+    /// Returns a view over the whole bit vector.
+    #[inline]
+    pub fn as_bit_slice(&self) -> BitSlice<'_, W> {
+        unsafe { BitSlice::from_raw_parts(&self.raw_data, 0, self.len) }
+    }
+
+    /// Returns a view over the `range` of the bit vector, without copying.
+    #[inline]
+    pub fn slice(&self, range: Range<usize>) -> BitSlice<'_, W> {
+        self.as_bit_slice().slice(range)
+    }
+}
+
+impl<'a, W: Word> From<&'a BitVec<W>> for BitSlice<'a, W> {
+    fn from(bitvec: &'a BitVec<W>) -> Self {
+        bitvec.as_bit_slice()
+    }
+}
+
+impl<W: Word> From<BitSlice<'_, W>> for BitVec<W> {
+    fn from(slice: BitSlice<'_, W>) -> Self {
+        slice.to_bitvec()
+    }
+}
+
+impl<W: Word> Access for BitSlice<'_, W> {
+    fn access(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+        Some(self.read(index))
+    }
+}
+
+impl<W: Word> Rank for BitSlice<'_, W> {
+    fn rank(&self, index: usize) -> Option<usize> {
+        // rank is the number of 1s before the index (range [0, index))
+        if index > self.len {
+            return None;
+        }
+
+        let (block_index, block_offset) = (index / W::BITS, index % W::BITS);
+
+        let prev_rank = (0..block_index)
+            .map(|i| unsafe { self.word_unchecked(i) }.count_ones() as usize)
+            .sum::<usize>();
+
+        let last_block_rank =
+            unsafe { self.read_bits_unchecked(block_index * W::BITS, block_offset) }.count_ones()
+                as usize;
+
+        Some(prev_rank + last_block_rank)
+    }
+}
+
+impl<W: Word> Select for BitSlice<'_, W> {
+    fn select(&self, rank: usize) -> Option<usize> {
+        if rank == 0 {
+            return Some(0);
+        }
+
+        let mut rank_count = 0;
+        for i in 0..self.num_words() {
+            let block = unsafe { self.word_unchecked(i) };
+            let block_rank = block.count_ones() as usize;
+            if rank_count + block_rank >= rank {
+                // Select inside word
+                let bit_index = select_in_word(block, rank - rank_count - 1) + 1;
+                return Some(i * W::BITS + bit_index);
+            }
+            rank_count += block_rank;
+        }
+        None
+    }
+
+    fn select0(&self, rank0: usize) -> Option<usize> {
+        if rank0 == 0 {
+            return Some(0);
+        }
+
+        let mut rank0_count = 0;
+        for i in 0..self.num_words() {
+            let block = unsafe { self.word_unchecked(i) };
+            // The last word may not be fully completed, so we do not count the padding 0s
+            let block_len = W::BITS.min(self.len - i * W::BITS);
+            let block_rank0 = block_len - block.count_ones() as usize;
+
+            if rank0_count + block_rank0 >= rank0 {
+                // Select inside word
+                let bit_index = select_in_word(!block, rank0 - rank0_count - 1) + 1;
+                return Some(i * W::BITS + bit_index);
+            }
+            rank0_count += block_rank0;
+        }
+
+        None
+    }
+}
+
+impl<W: Word> Debug for BitSlice<'_, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BitSlice")
+            .field("offset", &self.offset)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<W: Word> Display for BitSlice<'_, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut iter = self.iter();
+        write!(f, "[")?;
+        if let Some(bit) = iter.next() {
+            let bit = if bit { 1 } else { 0 };
+            write!(f, "{bit}")?;
+        }
+        for bit in iter {
+            let bit = if bit { 1 } else { 0 };
+            write!(f, ", {bit}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<'a, W: Word> IntoIterator for BitSlice<'a, W> {
+    type Item = bool;
+    type IntoIter = Iter<'a, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self)
+    }
+}
+
+impl<'a, W: Word> IntoIterator for &BitSlice<'a, W> {
+    type Item = bool;
+    type IntoIter = Iter<'a, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct Iter<'a, W: Word = usize> {
+    slice: BitSlice<'a, W>,
+    index: usize,
+}
+
+impl<'a, W: Word> Iter<'a, W> {
+    pub fn new(slice: BitSlice<'a, W>) -> Self {
+        Self { slice, index: 0 }
+    }
+}
+
+impl<W: Word> Iterator for Iter<'_, W> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.slice.len {
+            return None;
+        }
+        let val = self.slice.read(self.index);
+        self.index += 1;
+        Some(val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len() - self.index;
+        (len, Some(len))
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::test_utils::random_bitvec;
 use paste::paste;
 
 #[test]
@@ -291,4 +292,210 @@ macro_rules! test_for {
 
     }
 }
-test_for!(u8, u16, usize);
+test_for!(u8, u16, u32, usize);
+
+#[cfg(target_pointer_width = "64")]
+test_for!(u64);
+
+#[test]
+fn from_u128_slice() {
+    let value = 0xF0F0_0000_0000_0000_0000_0000_0000_00FF_u128;
+    let bitvec = BitVec::from(&[value, u128::MAX][..]);
+
+    assert_eq!(bitvec.len(), 256);
+    assert_eq!(bitvec.read_bits(0, 64), value as usize);
+    assert_eq!(bitvec.read_bits(64, 64), (value >> 64) as usize);
+    assert_eq!(bitvec.count_ones(128..256), 128);
+}
+
+#[test]
+fn from_u128_iterator() {
+    let value = 0xF0F0_0000_0000_0000_0000_0000_0000_00FF_u128;
+    let mut bitvec = [value].into_iter().collect::<BitVec>();
+    bitvec.extend([1u128].iter());
+
+    assert_eq!(bitvec.len(), 256);
+    assert_eq!(bitvec.iter_ones().collect::<Vec<_>>(), {
+        let mut ones = (0..8).collect::<Vec<_>>();
+        ones.extend([116, 117, 118, 119, 124, 125, 126, 127, 128]);
+        ones
+    });
+}
+
+#[test]
+fn from_bool_slice_with_random_values() {
+    for len in [1, 63, 64, 65, 1000] {
+        let bools = (0..len).map(|_| rand::random::<bool>()).collect::<Vec<_>>();
+        let bitvec = BitVec::from(&bools[..]);
+
+        assert_eq!(bitvec.len(), len);
+        assert_eq!(bitvec.iter().collect::<Vec<_>>(), bools);
+    }
+}
+
+#[test]
+fn extend_bool_when_not_aligned() {
+    let mut bitvec = BitVec::from([0b101u8]);
+    bitvec.pop_bits(5);
+    let bools = (0..200).map(|_| rand::random::<bool>()).collect::<Vec<_>>();
+    // filter hides the exact size of the iterator
+    bitvec.extend(bools.iter().copied().filter(|_| true));
+
+    let mut expected = vec![true, false, true];
+    expected.extend(&bools);
+    assert_eq!(bitvec.len(), 203);
+    assert_eq!(bitvec.iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn from_words() {
+    let words = vec![usize::MAX, 0b1011, usize::MAX];
+    let ptr = words.as_ptr();
+    let bitvec = BitVec::from_words(words, BitVec::CONTAINER_WIDTH + 3);
+
+    assert_eq!(bitvec.len(), BitVec::CONTAINER_WIDTH + 3);
+    assert_eq!(bitvec.raw_data(), &[usize::MAX, 0b011]);
+
+    // The buffer is not copied
+    let words = bitvec.into_words();
+    assert_eq!(words.as_ptr(), ptr);
+    assert_eq!(words, vec![usize::MAX, 0b011]);
+}
+
+#[test]
+fn from_words_when_word_is_u8() {
+    let bitvec = BitVec::from_words(vec![0b1111_0000u8, 0b1010_1010], 12);
+
+    assert_eq!(bitvec.len(), 12);
+    assert_eq!(bitvec.read_bits(0, 8), 0b1111_0000);
+    assert_eq!(bitvec.read_bits(8, 4), 0b1010);
+    assert_eq!(bitvec.into_words(), vec![0b1111_0000, 0b1010]);
+}
+
+#[test]
+#[should_panic]
+fn from_words_fails_when_len_is_greater_than_words() {
+    BitVec::from_words(vec![0u8, 0u8], 17);
+}
+
+#[test]
+fn iter_ones() {
+    let bitvec = BitVec::from([0b10100110u8]);
+    let ones = bitvec.iter_ones().collect::<Vec<_>>();
+
+    assert_eq!(ones, vec![1, 2, 5, 7]);
+}
+
+#[test]
+fn iter_zeros() {
+    let bitvec = BitVec::from([0b10100110u8]);
+    let zeros = bitvec.iter_zeros().collect::<Vec<_>>();
+
+    assert_eq!(zeros, vec![0, 3, 4, 6]);
+}
+
+#[test]
+fn iter_zeros_does_not_yield_positions_past_len() {
+    let bitvec = BitVec::from_value(false, 70);
+
+    assert_eq!(bitvec.iter_zeros().len(), 70);
+    assert_eq!(bitvec.iter_zeros().next_back(), Some(69));
+    assert_eq!(bitvec.iter_ones().next(), None);
+}
+
+#[test]
+fn iter_ones_when_empty_bitvec() {
+    let bitvec = BitVec::new();
+
+    assert_eq!(bitvec.iter_ones().next(), None);
+    assert_eq!(bitvec.iter_zeros().next_back(), None);
+}
+
+#[test]
+fn iter_ones_and_zeros_with_random_values() {
+    let bitvec = random_bitvec(1000);
+
+    let expected_ones = (0..bitvec.len())
+        .filter(|i| bitvec.read(*i))
+        .collect::<Vec<_>>();
+    let expected_zeros = (0..bitvec.len())
+        .filter(|i| !bitvec.read(*i))
+        .collect::<Vec<_>>();
+
+    assert_eq!(bitvec.iter_ones().collect::<Vec<_>>(), expected_ones);
+    assert_eq!(bitvec.iter_zeros().collect::<Vec<_>>(), expected_zeros);
+    assert_eq!(bitvec.iter_ones().len(), expected_ones.len());
+    assert_eq!(bitvec.iter_zeros().len(), expected_zeros.len());
+}
+
+#[test]
+fn iter_ones_and_zeros_range() {
+    let bitvec = random_bitvec(1000);
+
+    for (start, end) in [(0, 0), (0, 1000), (3, 5), (60, 70), (64, 128), (100, 901)] {
+        let expected_ones = (start..end).filter(|i| bitvec.read(*i)).collect::<Vec<_>>();
+        let expected_zeros = (start..end)
+            .filter(|i| !bitvec.read(*i))
+            .collect::<Vec<_>>();
+
+        let ones = bitvec.iter_ones_range(start..end);
+        assert_eq!(ones.len(), expected_ones.len());
+        assert_eq!(ones.collect::<Vec<_>>(), expected_ones);
+
+        let zeros = bitvec.iter_zeros_range(start..end);
+        assert_eq!(zeros.len(), expected_zeros.len());
+        assert_eq!(zeros.collect::<Vec<_>>(), expected_zeros);
+    }
+}
+
+#[test]
+fn iter_ones_and_zeros_are_double_ended() {
+    let bitvec = random_bitvec(1000);
+
+    let expected_ones = bitvec.iter_ones().collect::<Vec<_>>();
+    let reversed_ones = bitvec.iter_ones().rev().collect::<Vec<_>>();
+    assert_eq!(
+        reversed_ones,
+        expected_ones.iter().rev().copied().collect::<Vec<_>>()
+    );
+
+    let expected_zeros = bitvec.iter_zeros_range(5..700).collect::<Vec<_>>();
+    let reversed_zeros = bitvec.iter_zeros_range(5..700).rev().collect::<Vec<_>>();
+    assert_eq!(
+        reversed_zeros,
+        expected_zeros.iter().rev().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn iter_ones_alternating_ends() {
+    let bitvec = random_bitvec(1000);
+    let expected = bitvec.iter_ones_range(10..990).collect::<Vec<_>>();
+
+    let mut iter = bitvec.iter_ones_range(10..990);
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    loop {
+        assert_eq!(iter.len(), expected.len() - front.len() - back.len());
+        match iter.next() {
+            Some(position) => front.push(position),
+            None => break,
+        }
+        match iter.next_back() {
+            Some(position) => back.push(position),
+            None => break,
+        }
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    front.extend(back.into_iter().rev());
+    assert_eq!(front, expected);
+}
+
+#[test]
+#[should_panic]
+fn iter_ones_range_fails_when_range_out_of_bounds() {
+    let bitvec = BitVec::from_value(true, 10);
+    bitvec.iter_ones_range(5..11);
+}
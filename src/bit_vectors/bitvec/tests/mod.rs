@@ -1,8 +1,15 @@
 use crate::bit_vectors::rank_select::tests_utils::test_rank_select_access_for;
 
 use super::*;
+use crate::test_utils::random_bitvec;
 
 mod iter;
+mod ops;
+mod search;
+mod slice;
+mod text;
+mod transform;
+mod word;
 
 #[test]
 fn new() {
@@ -299,7 +306,7 @@ fn set_bits_fails_when_width_overflows_container() {
 
 #[test]
 fn default() {
-    let bitvec = BitVec::default();
+    let bitvec: BitVec = BitVec::default();
     assert_eq!(bitvec.len(), 0);
     assert_eq!(bitvec.raw_data.len(), 0);
 }
@@ -356,3 +363,193 @@ fn access() {
 }
 
 test_rank_select_access_for!(BitVec,);
+
+#[test]
+fn insert() {
+    let mut bitvec = BitVec::from([0b11110000u8]);
+    bitvec.insert(4, false);
+    bitvec.insert(0, true);
+
+    assert_eq!(bitvec.len(), 10);
+    assert_eq!(bitvec.read_bits(0, 10), 0b1111000001);
+}
+
+#[test]
+fn insert_at_end() {
+    let mut bitvec = BitVec::from_value(false, 64);
+    bitvec.insert(64, true);
+
+    assert_eq!(bitvec.len(), 65);
+    assert_eq!(bitvec.raw_data(), &[0, 1]);
+}
+
+#[test]
+#[should_panic]
+fn insert_fails_when_index_out_of_bounds() {
+    let mut bitvec = BitVec::from_value(false, 8);
+    bitvec.insert(9, true);
+}
+
+#[test]
+fn insert_when_data_spans_more_than_one_word() {
+    let mut bitvec = random_bitvec(1000);
+    let mut expected = bitvec.iter().collect::<Vec<_>>();
+
+    for (i, index) in [0, 1, 63, 64, 65, 500, 1000].into_iter().enumerate() {
+        let value = i % 2 == 0;
+        bitvec.insert(index, value);
+        expected.insert(index, value);
+        assert_eq!(bitvec, BitVec::from(expected.clone()));
+    }
+}
+
+#[test]
+fn insert_bits() {
+    let mut bitvec = BitVec::from_value(false, 130);
+    bitvec.insert_bits(60, 0b1011u8, 4);
+
+    assert_eq!(bitvec.len(), 134);
+    assert_eq!(bitvec.read_bits(56, 12), 0b1011_0000);
+    assert_eq!(bitvec.rank(134), Some(3));
+
+    bitvec.insert_bits(3, usize::MAX, 64);
+    assert_eq!(bitvec.len(), 198);
+    assert_eq!(bitvec.read_bits(3, 64), usize::MAX);
+    assert_eq!(bitvec.read_bits(0, 3), 0);
+    assert_eq!(bitvec.read_bits(120, 12), 0b1011_0000);
+}
+
+#[test]
+fn remove() {
+    let mut bitvec = BitVec::from([0b11110000u8]);
+
+    assert!(bitvec.remove(4));
+    assert!(!bitvec.remove(0));
+    assert_eq!(bitvec.len(), 6);
+    assert_eq!(bitvec.read_bits(0, 6), 0b111000);
+}
+
+#[test]
+#[should_panic]
+fn remove_fails_when_index_out_of_bounds() {
+    let mut bitvec = BitVec::from_value(false, 8);
+    bitvec.remove(8);
+}
+
+#[test]
+fn remove_when_data_spans_more_than_one_word() {
+    let mut bitvec = random_bitvec(1000);
+    let mut expected = bitvec.iter().collect::<Vec<_>>();
+
+    for index in [0, 1, 63, 64, 65, 500, 993] {
+        assert_eq!(bitvec.remove(index), expected.remove(index));
+        assert_eq!(bitvec, BitVec::from(expected.clone()));
+    }
+}
+
+#[test]
+fn drain() {
+    let bitvec = random_bitvec(1000);
+    let bits = bitvec.iter().collect::<Vec<_>>();
+
+    for (start, end) in [
+        (0, 0),
+        (0, 1000),
+        (3, 70),
+        (64, 128),
+        (100, 900),
+        (999, 1000),
+    ] {
+        let mut drained_bitvec = bitvec.clone();
+        let mut expected = bits.clone();
+
+        let removed = drained_bitvec.drain(start..end);
+        let expected_removed = expected.drain(start..end).collect::<Vec<_>>();
+
+        assert_eq!(removed, BitVec::from(expected_removed));
+        assert_eq!(drained_bitvec, BitVec::from(expected));
+    }
+}
+
+#[test]
+fn splice() {
+    let bitvec = random_bitvec(500);
+    let replacement = random_bitvec(150);
+    let bits = bitvec.iter().collect::<Vec<_>>();
+
+    for (start, end, len) in [
+        (0, 0, 10),
+        (10, 20, 150),
+        (64, 300, 7),
+        (100, 250, 150),
+        (500, 500, 65),
+    ] {
+        let replace_with = replacement.slice(0..len).to_bitvec();
+        let mut spliced_bitvec = bitvec.clone();
+        let mut expected = bits.clone();
+
+        let removed = spliced_bitvec.splice(start..end, &replace_with);
+        let expected_removed = expected
+            .splice(start..end, replace_with.iter())
+            .collect::<Vec<_>>();
+
+        assert_eq!(removed, BitVec::from(expected_removed));
+        assert_eq!(spliced_bitvec, BitVec::from(expected));
+    }
+}
+
+#[test]
+fn split_off() {
+    let mut bitvec = random_bitvec(200);
+    let expected = bitvec.clone();
+
+    let tail = bitvec.split_off(70);
+
+    assert_eq!(bitvec.len(), 70);
+    assert_eq!(tail.len(), 130);
+    assert_eq!(bitvec, expected.slice(0..70).to_bitvec());
+    assert_eq!(tail, expected.slice(70..200).to_bitvec());
+
+    let empty_tail = bitvec.split_off(70);
+    assert!(empty_tail.is_empty());
+}
+
+#[test]
+fn append() {
+    let mut bitvec = random_bitvec(70);
+    let mut other = random_bitvec(130);
+    let expected = bitvec.iter().chain(other.iter()).collect::<BitVec>();
+
+    bitvec.append(&mut other);
+
+    assert_eq!(bitvec, expected);
+    assert!(other.is_empty());
+    assert_eq!(other.len(), 0);
+}
+
+#[test]
+fn split_off_is_the_inverse_of_append() {
+    let mut bitvec = random_bitvec(300);
+    let expected = bitvec.clone();
+
+    let mut tail = bitvec.split_off(129);
+    bitvec.append(&mut tail);
+
+    assert_eq!(bitvec, expected);
+}
+
+#[test]
+fn truncate() {
+    let mut bitvec = BitVec::from_value(true, 200);
+
+    bitvec.truncate(300);
+    assert_eq!(bitvec.len(), 200);
+
+    bitvec.truncate(70);
+    assert_eq!(bitvec, BitVec::from_value(true, 70));
+    // There must not be dirty bits past len
+    assert_eq!(bitvec.raw_data(), &[usize::MAX, 0b111111]);
+
+    bitvec.clear();
+    assert!(bitvec.is_empty());
+}
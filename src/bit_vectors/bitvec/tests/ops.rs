@@ -0,0 +1,131 @@
+use super::*;
+
+#[test]
+fn bitand() {
+    let a = BitVec::from([0b11001100u8]);
+    let b = BitVec::from([0b10101010u8]);
+
+    let result = &a & &b;
+    assert_eq!(result.len(), 8);
+    assert_eq!(result.read_bits(0, 8), 0b10001000);
+}
+
+#[test]
+fn bitor() {
+    let a = BitVec::from([0b11001100u8]);
+    let b = BitVec::from([0b10101010u8]);
+
+    let result = &a | &b;
+    assert_eq!(result.len(), 8);
+    assert_eq!(result.read_bits(0, 8), 0b11101110);
+}
+
+#[test]
+fn bitxor() {
+    let a = BitVec::from([0b11001100u8]);
+    let b = BitVec::from([0b10101010u8]);
+
+    let result = a ^ b;
+    assert_eq!(result.len(), 8);
+    assert_eq!(result.read_bits(0, 8), 0b01100110);
+}
+
+#[test]
+fn and_not() {
+    let a = BitVec::from([0b11001100u8]);
+    let b = BitVec::from([0b10101010u8]);
+
+    let result = a.and_not(&b);
+    assert_eq!(result.len(), 8);
+    assert_eq!(result.read_bits(0, 8), 0b01000100);
+}
+
+#[test]
+fn assign_ops() {
+    let b = BitVec::from([0b10101010u8]);
+
+    let mut result = BitVec::from([0b11001100u8]);
+    result &= &b;
+    assert_eq!(result.read_bits(0, 8), 0b10001000);
+
+    let mut result = BitVec::from([0b11001100u8]);
+    result |= &b;
+    assert_eq!(result.read_bits(0, 8), 0b11101110);
+
+    let mut result = BitVec::from([0b11001100u8]);
+    result ^= b.clone();
+    assert_eq!(result.read_bits(0, 8), 0b01100110);
+
+    let mut result = BitVec::from([0b11001100u8]);
+    result.and_not_assign(&b);
+    assert_eq!(result.read_bits(0, 8), 0b01000100);
+}
+
+#[test]
+fn not() {
+    let bitvec = BitVec::from([0b11001100u8]);
+
+    let result = !&bitvec;
+    assert_eq!(result.len(), 8);
+    assert_eq!(result.read_bits(0, 8), 0b00110011);
+    // There must not be dirty bits past len
+    assert_eq!(result.raw_data(), &[0b00110011]);
+    assert_eq!(!result, bitvec);
+}
+
+#[test]
+fn ops_when_data_spans_more_than_one_word() {
+    let a = BitVec::from([usize::MAX, 0, 0b1100]);
+    let b = BitVec::from([0b1010, usize::MAX, 0b1010]);
+
+    assert_eq!((&a & &b).raw_data(), &[0b1010, 0, 0b1000]);
+    assert_eq!((&a | &b).raw_data(), &[usize::MAX, usize::MAX, 0b1110]);
+    assert_eq!((&a ^ &b).raw_data(), &[!0b1010, usize::MAX, 0b0110]);
+    assert_eq!(a.and_not(&b).raw_data(), &[!0b1010, 0, 0b0100]);
+}
+
+#[test]
+fn ops_when_lengths_differ() {
+    // The shorter operand is padded with 0s
+    let short = BitVec::from([true, true, false, true]);
+    let long = BitVec::from_value(true, 100);
+
+    let and = &short & &long;
+    assert_eq!(and.len(), 100);
+    assert_eq!(and.read_bits(0, 4), 0b1011);
+    assert_eq!(and.rank(100), Some(3));
+
+    let or = &short | &long;
+    assert_eq!(or, long);
+
+    let xor = &long ^ &short;
+    assert_eq!(xor.len(), 100);
+    assert_eq!(xor.read_bits(0, 4), 0b0100);
+    assert_eq!(xor.rank(100), Some(97));
+
+    let and_not = long.and_not(&short);
+    assert_eq!(and_not, xor);
+
+    let and_not = short.and_not(&long);
+    assert_eq!(and_not, BitVec::from_value(false, 100));
+}
+
+#[test]
+fn ops_when_empty() {
+    let empty = BitVec::new();
+    let bitvec = BitVec::from([0b1011u8]);
+
+    assert_eq!(&empty & &empty, empty);
+    assert_eq!(!&empty, empty);
+    assert_eq!(&empty | &bitvec, bitvec);
+    assert_eq!(&bitvec & &empty, BitVec::from_value(false, 8));
+}
+
+#[test]
+fn not_keeps_no_dirty_bits_past_len() {
+    let bitvec = BitVec::from_value(false, 70);
+    let result = !bitvec;
+
+    assert_eq!(result, BitVec::from_value(true, 70));
+    assert_eq!(result.raw_data()[1], 0b111111);
+}
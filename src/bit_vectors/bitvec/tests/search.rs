@@ -0,0 +1,107 @@
+use super::*;
+use crate::test_utils::random_bools;
+
+#[test]
+fn count_ones_and_zeros() {
+    let bitvec = BitVec::from([0b11001101u8]);
+
+    assert_eq!(bitvec.count_ones(0..8), 5);
+    assert_eq!(bitvec.count_ones(1..4), 2);
+    assert_eq!(bitvec.count_ones(4..4), 0);
+    assert_eq!(bitvec.count_zeros(0..8), 3);
+    assert_eq!(bitvec.count_zeros(1..4), 1);
+    assert_eq!(bitvec.count_zeros(4..4), 0);
+}
+
+#[test]
+fn first_and_last_one() {
+    let bitvec = BitVec::from([0b01001100u8]);
+
+    assert_eq!(bitvec.first_one(0..8), Some(2));
+    assert_eq!(bitvec.first_one(3..8), Some(3));
+    assert_eq!(bitvec.first_one(4..6), None);
+    assert_eq!(bitvec.last_one(0..8), Some(6));
+    assert_eq!(bitvec.last_one(0..6), Some(3));
+    assert_eq!(bitvec.last_one(0..2), None);
+    assert_eq!(bitvec.last_one(2..2), None);
+}
+
+#[test]
+fn next_and_prev_one() {
+    let bitvec = BitVec::from([0b01001100u8]);
+
+    assert_eq!(bitvec.next_one(0), Some(2));
+    assert_eq!(bitvec.next_one(2), Some(2));
+    assert_eq!(bitvec.next_one(4), Some(6));
+    assert_eq!(bitvec.next_one(7), None);
+    assert_eq!(bitvec.next_one(100), None);
+    assert_eq!(bitvec.prev_one(7), Some(6));
+    assert_eq!(bitvec.prev_one(5), Some(3));
+    assert_eq!(bitvec.prev_one(1), None);
+    assert_eq!(bitvec.prev_one(100), Some(6));
+}
+
+#[test]
+#[should_panic]
+fn count_ones_fails_when_out_of_bounds() {
+    let bitvec = BitVec::from([0b01001100u8]);
+    bitvec.count_ones(2..9);
+}
+
+#[test]
+#[should_panic]
+fn first_one_fails_when_out_of_bounds() {
+    let bitvec = BitVec::from([0b01001100u8]);
+    bitvec.first_one(2..9);
+}
+
+#[test]
+fn search_when_empty() {
+    let bitvec: BitVec = BitVec::new();
+
+    assert_eq!(bitvec.count_ones(0..0), 0);
+    assert_eq!(bitvec.count_zeros(0..0), 0);
+    assert_eq!(bitvec.first_one(0..0), None);
+    assert_eq!(bitvec.last_one(0..0), None);
+    assert_eq!(bitvec.next_one(0), None);
+    assert_eq!(bitvec.prev_one(0), None);
+}
+
+macro_rules! test_search_for_word {
+    ($($t:ty),*) => {
+        $(
+            paste::paste! {
+                #[test]
+                fn [<search_with_random_values_when_word_is_ $t>]() {
+                    // Sparse bits, so the searches need to skip whole words
+                    let bools = random_bools(1000)
+                        .into_iter()
+                        .map(|bit| bit && rand::random::<u8>() < 16)
+                        .collect::<Vec<_>>();
+                    let bitvec = bools.iter().copied().collect::<BitVec<$t>>();
+                    let ones = (0..bools.len()).filter(|i| bools[*i]).collect::<Vec<_>>();
+
+                    for _ in 0..200 {
+                        let a = rand::random::<usize>() % (bools.len() + 1);
+                        let b = rand::random::<usize>() % (bools.len() + 1);
+                        let range = a.min(b)..a.max(b);
+                        let in_range = ones
+                            .iter()
+                            .copied()
+                            .filter(|i| range.contains(i))
+                            .collect::<Vec<_>>();
+
+                        assert_eq!(bitvec.count_ones(range.clone()), in_range.len());
+                        assert_eq!(bitvec.count_zeros(range.clone()), range.len() - in_range.len());
+                        assert_eq!(bitvec.first_one(range.clone()), in_range.first().copied());
+                        assert_eq!(bitvec.last_one(range.clone()), in_range.last().copied());
+                        assert_eq!(bitvec.next_one(a), ones.iter().copied().find(|i| *i >= a));
+                        assert_eq!(bitvec.prev_one(a), ones.iter().copied().rev().find(|i| *i <= a));
+                    }
+                }
+            }
+        )*
+    };
+}
+
+test_search_for_word!(u8, u32, usize, u128);
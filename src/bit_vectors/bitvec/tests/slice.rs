@@ -0,0 +1,130 @@
+use super::*;
+use crate::test_utils::random_bitvec;
+
+const BIG_BITVEC_SIZE: usize = 1_000;
+
+#[test]
+fn slice() {
+    let bitvec = BitVec::from([0b11110000u8, 0b10101010]);
+    let slice = bitvec.slice(2..12);
+
+    assert_eq!(slice.len(), 10);
+    assert!(!slice.read(0));
+    assert!(!slice.read(1));
+    assert!(slice.read(2));
+    assert_eq!(slice.read_bits(0, 10), 0b1010111100);
+}
+
+#[test]
+fn slice_when_empty() {
+    let bitvec = BitVec::from([0b11110000u8]);
+    let slice = bitvec.slice(8..8);
+
+    assert!(slice.is_empty());
+    assert_eq!(slice.to_bitvec(), BitVec::new());
+    assert_eq!(slice.rank(0), Some(0));
+    assert_eq!(slice.select(1), None);
+    assert_eq!(slice.access(0), None);
+}
+
+#[test]
+#[should_panic]
+fn slice_fails_when_range_out_of_bounds() {
+    let bitvec = BitVec::from([0b11110000u8]);
+    bitvec.slice(4..9);
+}
+
+#[test]
+fn slice_of_slice() {
+    let bitvec = BitVec::from([0b11110000u8, 0b10101010]);
+    let slice = bitvec.slice(2..12);
+    let sub_slice = slice.slice(2..6);
+
+    assert_eq!(sub_slice.len(), 4);
+    assert_eq!(sub_slice.read_bits(0, 4), 0b1111);
+    assert_eq!(
+        sub_slice.to_bitvec(),
+        BitVec::from([true, true, true, true])
+    );
+}
+
+#[test]
+#[should_panic]
+fn read_fails_when_index_out_of_bounds() {
+    let bitvec = BitVec::from([0b11110000u8]);
+    bitvec.slice(0..4).read(4);
+}
+
+#[test]
+fn read_bits_when_range_spans_two_words() {
+    let bitvec = BitVec::from([0usize, usize::MAX, 0]);
+    let slice = bitvec.slice(3..150);
+
+    assert_eq!(slice.read_bits(57, 8), 0b11110000);
+    assert_eq!(slice.read_bits(61, 64), usize::MAX);
+    assert_eq!(slice.read_bits(121, 8), 0b00001111);
+}
+
+#[test]
+fn to_bitvec() {
+    let bitvec = random_bitvec(BIG_BITVEC_SIZE);
+    for (start, end) in [(0, BIG_BITVEC_SIZE), (1, 65), (63, 129), (100, 777), (5, 6)] {
+        let slice = bitvec.slice(start..end);
+        let expected = (start..end).map(|i| bitvec.read(i)).collect::<BitVec>();
+        assert_eq!(slice.to_bitvec(), expected);
+        assert_eq!(BitVec::from(slice), expected);
+    }
+}
+
+#[test]
+fn iter() {
+    let bitvec = random_bitvec(BIG_BITVEC_SIZE);
+    let slice = bitvec.slice(37..901);
+
+    let mut iter = slice.iter();
+    assert_eq!(iter.size_hint(), (864, Some(864)));
+    iter.next();
+    assert_eq!(iter.size_hint(), (863, Some(863)));
+
+    let bits = slice.iter().collect::<Vec<_>>();
+    let expected = bitvec.iter().skip(37).take(864).collect::<Vec<_>>();
+    assert_eq!(bits, expected);
+}
+
+#[test]
+fn rank_select_access_match_copied_bitvec() {
+    let bitvec = random_bitvec(BIG_BITVEC_SIZE);
+    for (start, end) in [(0, BIG_BITVEC_SIZE), (1, 65), (63, 129), (100, 777)] {
+        let slice = bitvec.slice(start..end);
+        let copied = slice.to_bitvec();
+
+        for i in 0..=slice.len() {
+            assert_eq!(slice.rank(i), copied.rank(i));
+            assert_eq!(slice.rank0(i), copied.rank0(i));
+            assert_eq!(slice.access(i), copied.access(i));
+        }
+        for i in 0..=slice.len() + 1 {
+            assert_eq!(slice.select(i), copied.select(i));
+            assert_eq!(slice.select0(i), copied.select0(i));
+        }
+    }
+}
+
+#[test]
+fn rank_when_index_is_out_of_bounds() {
+    let bitvec = BitVec::from_value(true, 100);
+    let slice = bitvec.slice(10..20);
+
+    assert_eq!(slice.rank(10), Some(10));
+    assert_eq!(slice.rank(11), None);
+}
+
+#[test]
+fn select_does_not_read_past_the_view() {
+    let bitvec = BitVec::from_value(true, 100);
+    let slice = bitvec.slice(10..20);
+
+    assert_eq!(slice.select(10), Some(10));
+    assert_eq!(slice.select(11), None);
+    assert_eq!(slice.select0(1), None);
+}
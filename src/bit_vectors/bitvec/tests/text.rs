@@ -0,0 +1,92 @@
+use super::*;
+
+#[test]
+fn parse() {
+    let bitvec = "0110_1001".parse::<BitVec>().unwrap();
+
+    assert_eq!(bitvec.len(), 8);
+    assert_eq!(bitvec.read_bits(0, 8), 0b10010110);
+}
+
+#[test]
+fn parse_with_separators() {
+    let expected = BitVec::from([true, false, false, true, true]);
+
+    assert_eq!("10011".parse::<BitVec>().unwrap(), expected);
+    assert_eq!(" 1 0 0\t1 1\n".parse::<BitVec>().unwrap(), expected);
+    assert_eq!("1,0,0,1,1".parse::<BitVec>().unwrap(), expected);
+    assert_eq!("[1, 0, 0, 1, 1]".parse::<BitVec>().unwrap(), expected);
+}
+
+#[test]
+fn parse_when_empty() {
+    assert!("".parse::<BitVec>().unwrap().is_empty());
+    assert!("[]".parse::<BitVec>().unwrap().is_empty());
+    assert!(" _ ".parse::<BitVec>().unwrap().is_empty());
+}
+
+#[test]
+fn parse_fails_when_char_is_invalid() {
+    let error = "0110_2001".parse::<BitVec>().unwrap_err();
+    assert_eq!(error.index(), 5);
+    assert_eq!(error.found(), '2');
+    assert_eq!(
+        error.to_string(),
+        "invalid character '2' at index 5, expected '0', '1' or a separator"
+    );
+
+    // The index is relative to the whole string, brackets included
+    let error = " [1, x]".parse::<BitVec>().unwrap_err();
+    assert_eq!(error.index(), 5);
+    assert_eq!(error.found(), 'x');
+
+    // Unbalanced brackets are not separators
+    assert_eq!("[101".parse::<BitVec>().unwrap_err().found(), '[');
+}
+
+#[test]
+fn format_binary() {
+    let bitvec = BitVec::from([0b10010110u8, 0b1]);
+
+    assert_eq!(format!("{bitvec:b}"), "0110100110000000");
+    assert_eq!(format!("{:b}", BitVec::new()), "");
+}
+
+#[test]
+fn format_word_dump() {
+    let mut bitvec = "0110_1001_1101".parse::<BitVec<u8>>().unwrap();
+
+    assert_eq!(format!("{bitvec:#b}"), "10010110 00001011");
+    assert_eq!(format!("{bitvec:x}"), "96 0b");
+    assert_eq!(format!("{bitvec:#x}"), "0x96 0x0b");
+
+    bitvec.clear();
+    assert_eq!(format!("{bitvec:#b}"), "");
+    assert_eq!(format!("{bitvec:x}"), "");
+}
+
+#[test]
+fn format_word_dump_when_word_is_usize() {
+    let bitvec = BitVec::from([usize::MAX, 0b1010]);
+    let width = BitVec::CONTAINER_WIDTH / 4;
+
+    assert_eq!(
+        format!("{bitvec:x}"),
+        format!("{:x} {:0width$x}", usize::MAX, 0b1010)
+    );
+}
+
+#[test]
+fn parse_is_the_inverse_of_format() {
+    for len in [0, 1, 63, 64, 65, 200] {
+        let bitvec = (0..len)
+            .map(|_| rand::random::<bool>())
+            .collect::<BitVec<u32>>();
+
+        assert_eq!(bitvec.to_string().parse::<BitVec<u32>>().unwrap(), bitvec);
+        assert_eq!(
+            format!("{bitvec:b}").parse::<BitVec<u32>>().unwrap(),
+            bitvec
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use super::*;
+use crate::test_utils::random_bools;
+
+const LENGTHS: [usize; 7] = [1, 7, 63, 64, 65, 200, 1000];
+
+fn to_bools<W: Word>(bitvec: &BitVec<W>) -> Vec<bool> {
+    bitvec.iter().collect()
+}
+
+#[test]
+fn shift_left() {
+    let mut bitvec = BitVec::from([0b00110011u8]);
+    bitvec.shift_left(3);
+
+    assert_eq!(bitvec.len(), 8);
+    assert_eq!(bitvec.read_bits(0, 8), 0b10011000);
+}
+
+#[test]
+fn shift_right() {
+    let mut bitvec = BitVec::from([0b00110011u8]);
+    bitvec.shift_right(3);
+
+    assert_eq!(bitvec.len(), 8);
+    assert_eq!(bitvec.read_bits(0, 8), 0b00000110);
+}
+
+#[test]
+fn rotate_left() {
+    let mut bitvec = BitVec::from([0b11000011u8]);
+    bitvec.rotate_left(3);
+
+    assert_eq!(bitvec.len(), 8);
+    assert_eq!(bitvec.read_bits(0, 8), 0b00011110);
+}
+
+#[test]
+fn rotate_right() {
+    let mut bitvec = BitVec::from([0b11000011u8]);
+    bitvec.rotate_right(3);
+
+    assert_eq!(bitvec.len(), 8);
+    assert_eq!(bitvec.read_bits(0, 8), 0b01111000);
+}
+
+#[test]
+fn reverse() {
+    let mut bitvec = BitVec::from([0b11000101u8]);
+    bitvec.pop();
+    bitvec.reverse();
+
+    assert_eq!(bitvec.len(), 7);
+    assert_eq!(bitvec.read_bits(0, 7), 0b1010001);
+}
+
+#[test]
+fn fill_range() {
+    let mut bitvec = BitVec::from([0b11001100u8]);
+    bitvec.fill_range(1..4, true);
+    bitvec.fill_range(6..7, false);
+
+    assert_eq!(bitvec.len(), 8);
+    assert_eq!(bitvec.read_bits(0, 8), 0b10001110);
+}
+
+#[test]
+#[should_panic]
+fn fill_range_fails_when_out_of_bounds() {
+    let mut bitvec = BitVec::from([0b11001100u8]);
+    bitvec.fill_range(4..9, true);
+}
+
+#[test]
+fn transforms_when_empty() {
+    let mut bitvec: BitVec = BitVec::new();
+    bitvec.shift_left(3);
+    bitvec.shift_right(3);
+    bitvec.rotate_left(3);
+    bitvec.rotate_right(3);
+    bitvec.reverse();
+    bitvec.fill_range(0..0, true);
+
+    assert!(bitvec.is_empty());
+}
+
+#[test]
+fn shifts_with_random_values() {
+    for len in LENGTHS {
+        let bools = random_bools(len);
+        let bitvec = bools.iter().copied().collect::<BitVec>();
+        for n in [0, 1, 5, 63, 64, 65, 130, len] {
+            let mut shifted = bitvec.clone();
+            shifted.shift_left(n);
+            let expected = (0..len).map(|i| i >= n && bools[i - n]).collect::<Vec<_>>();
+            assert_eq!(to_bools(&shifted), expected);
+            // The bits past len must be cleared
+            assert_eq!(shifted, expected.iter().copied().collect::<BitVec>());
+
+            let mut shifted = bitvec.clone();
+            shifted.shift_right(n);
+            let expected = (0..len)
+                .map(|i| i + n < len && bools[i + n])
+                .collect::<Vec<_>>();
+            assert_eq!(to_bools(&shifted), expected);
+        }
+    }
+}
+
+#[test]
+fn rotations_with_random_values() {
+    for len in LENGTHS {
+        let bools = random_bools(len);
+        let bitvec = bools.iter().copied().collect::<BitVec>();
+        for n in [0, 1, 5, 63, 64, 65, 130, len, 3 * len + 1] {
+            let mut rotated = bitvec.clone();
+            rotated.rotate_left(n);
+            let mut expected = bools.clone();
+            expected.rotate_right(n % len);
+            assert_eq!(to_bools(&rotated), expected);
+
+            rotated.rotate_right(n);
+            assert_eq!(rotated, bitvec);
+        }
+    }
+}
+
+#[test]
+fn reverse_with_random_values() {
+    for len in LENGTHS {
+        let bools = random_bools(len);
+        let mut bitvec = bools.iter().copied().collect::<BitVec>();
+        bitvec.reverse();
+
+        let expected = bools.iter().rev().copied().collect::<Vec<_>>();
+        assert_eq!(to_bools(&bitvec), expected);
+        assert_eq!(bitvec, expected.iter().copied().collect::<BitVec>());
+    }
+}
+
+#[test]
+fn fill_range_with_random_values() {
+    for len in LENGTHS {
+        let bools = random_bools(len);
+        let bitvec = bools.iter().copied().collect::<BitVec>();
+        for range in [0..len, 0..len / 2, len / 3..len, len / 3..len / 3 + 1] {
+            for value in [false, true] {
+                let mut filled = bitvec.clone();
+                filled.fill_range(range.clone(), value);
+
+                let mut expected = bools.clone();
+                expected[range.clone()].fill(value);
+                assert_eq!(to_bools(&filled), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn transforms_when_word_is_u8() {
+    let bools = random_bools(37);
+    let mut bitvec = bools.iter().copied().collect::<BitVec<u8>>();
+
+    bitvec.reverse();
+    bitvec.rotate_left(11);
+    bitvec.shift_right(2);
+
+    let mut expected = bools.iter().rev().copied().collect::<Vec<_>>();
+    expected.rotate_right(11);
+    expected.drain(..2);
+    expected.extend([false, false]);
+    assert_eq!(to_bools(&bitvec), expected);
+}
+
+#[test]
+fn rotations_when_word_is_u128() {
+    for len in [1, 127, 128, 129, 300] {
+        let bools = random_bools(len);
+        let bitvec = bools.iter().copied().collect::<BitVec<u128>>();
+        for n in [1, 64, 127, 128, 129, len - 1] {
+            let mut rotated = bitvec.clone();
+            rotated.rotate_left(n);
+            let mut expected = bools.clone();
+            expected.rotate_right(n % len);
+            assert_eq!(to_bools(&rotated), expected);
+
+            rotated.rotate_right(n);
+            assert_eq!(rotated, bitvec);
+        }
+    }
+}
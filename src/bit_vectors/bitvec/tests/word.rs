@@ -0,0 +1,155 @@
+use crate::bit_vectors::rank_select::{Rank, Select};
+use crate::bit_vectors::BitVec;
+use crate::test_utils::random_bools;
+use crate::util::Word;
+
+macro_rules! test_word_for {
+    ($($t:ty),*) => {
+        $(
+            paste::paste! {
+                mod [<when_word_is_ $t>] {
+                    use super::*;
+
+                    const BITS: usize = <$t>::BITS as usize;
+
+                    fn bitvec_from(bools: &[bool]) -> BitVec<$t> {
+                        bools.iter().copied().collect()
+                    }
+
+                    #[test]
+                    fn word_width() {
+                        let bitvec = BitVec::<$t>::filled(true, BITS + 1);
+                        assert_eq!(bitvec.raw_data().len(), 2);
+                        assert_eq!(bitvec.raw_data()[0], <$t>::MAX);
+                    }
+
+                    #[test]
+                    fn push_and_read() {
+                        let bools = random_bools(BITS * 5 + 3);
+                        let bitvec = bitvec_from(&bools);
+
+                        assert_eq!(bitvec.len(), bools.len());
+                        assert_eq!(bitvec.raw_data().len(), 6);
+                        for (i, bit) in bools.iter().enumerate() {
+                            assert_eq!(bitvec.read(i), *bit);
+                        }
+                    }
+
+                    #[test]
+                    fn push_bits_and_read_bits_across_words() {
+                        let mut bitvec = BitVec::<$t>::default();
+                        let mut values = Vec::new();
+                        for width in (1..=BITS).chain(1..=BITS) {
+                            let value = rand::random::<$t>() & crate::util::bitmask!($t, width, 0);
+                            bitvec.push_bits(value, width);
+                            values.push((value, width));
+                        }
+
+                        let mut index = 0;
+                        for (value, width) in values {
+                            assert_eq!(bitvec.read_bits(index, width), value);
+                            index += width;
+                        }
+                        assert_eq!(bitvec.len(), index);
+                    }
+
+                    #[test]
+                    fn set_bits_and_pop_bits() {
+                        let mut bitvec = BitVec::<$t>::filled(false, BITS * 3);
+                        bitvec.set_bits(BITS / 2..BITS / 2 + BITS, <$t>::MAX);
+
+                        assert_eq!(bitvec.read_bits(0, BITS / 2), 0);
+                        assert_eq!(bitvec.read_bits(BITS / 2, BITS), <$t>::MAX);
+                        assert_eq!(bitvec.pop_bits(BITS), 0);
+                        assert_eq!(bitvec.pop_bits(BITS), <$t>::MAX >> (BITS / 2));
+                        assert_eq!(bitvec.len(), BITS);
+                    }
+
+                    #[test]
+                    fn rank_and_select() {
+                        let bools = random_bools(BITS * 7 + 5);
+                        let bitvec = bitvec_from(&bools);
+
+                        let mut ones = 0;
+                        let mut zeros = 0;
+                        for (i, bit) in bools.iter().enumerate() {
+                            assert_eq!(bitvec.rank(i), Some(ones));
+                            if *bit {
+                                ones += 1;
+                                assert_eq!(bitvec.select(ones), Some(i + 1));
+                            } else {
+                                zeros += 1;
+                                assert_eq!(bitvec.select0(zeros), Some(i + 1));
+                            }
+                        }
+                        assert_eq!(bitvec.rank(bools.len()), Some(ones));
+                        assert_eq!(bitvec.select(ones + 1), None);
+                        assert_eq!(bitvec.select0(zeros + 1), None);
+                    }
+
+                    #[test]
+                    fn iter_ones_and_zeros() {
+                        let bools = random_bools(BITS * 4 + 1);
+                        let bitvec = bitvec_from(&bools);
+
+                        let ones = (0..bools.len()).filter(|i| bools[*i]).collect::<Vec<_>>();
+                        let zeros = (0..bools.len()).filter(|i| !bools[*i]).collect::<Vec<_>>();
+                        assert_eq!(bitvec.iter_ones().collect::<Vec<_>>(), ones);
+                        assert_eq!(bitvec.iter_zeros().rev().collect::<Vec<_>>(), zeros.into_iter().rev().collect::<Vec<_>>());
+                    }
+
+                    #[test]
+                    fn insert_and_remove() {
+                        let mut bools = random_bools(BITS * 3 + 1);
+                        let mut bitvec = bitvec_from(&bools);
+
+                        bitvec.insert(BITS - 1, true);
+                        bools.insert(BITS - 1, true);
+                        assert_eq!(bitvec.remove(1), bools.remove(1));
+                        assert_eq!(bitvec.iter().collect::<Vec<_>>(), bools);
+                    }
+
+                    #[test]
+                    fn bitwise_ops() {
+                        let left = random_bools(BITS * 3 + 7);
+                        let right = random_bools(BITS * 2 + 1);
+
+                        let result = &bitvec_from(&left) ^ &bitvec_from(&right);
+                        let expected = left
+                            .iter()
+                            .enumerate()
+                            .map(|(i, bit)| *bit ^ right.get(i).copied().unwrap_or(false))
+                            .collect::<Vec<_>>();
+                        assert_eq!(result.iter().collect::<Vec<_>>(), expected);
+                        assert_eq!((!result).len(), left.len());
+                    }
+
+                    #[test]
+                    fn slice() {
+                        let bools = random_bools(BITS * 3);
+                        let bitvec = bitvec_from(&bools);
+                        let slice = bitvec.slice(3..BITS * 2 + 1);
+
+                        assert_eq!(slice.iter().collect::<Vec<_>>(), &bools[3..BITS * 2 + 1]);
+                        assert_eq!(slice.read_bits(1, BITS), bitvec.read_bits(4, BITS));
+                        assert_eq!(slice.to_bitvec().rank(BITS), bitvec.slice(3..BITS + 3).rank(BITS));
+                    }
+                }
+            }
+        )*
+    };
+}
+
+test_word_for!(u8, u16, u32, u64, u128);
+
+#[test]
+fn read_bits_of_u128_words_returns_values_wider_than_usize() {
+    let value = u128::MAX - 5;
+    let mut bitvec = BitVec::<u128>::default();
+    bitvec.push(true);
+    bitvec.push_bits(value, 128);
+
+    assert_eq!(bitvec.read_bits(1, 128), value);
+    assert_eq!(bitvec.len(), 129);
+    assert_eq!(<u128 as Word>::BITS, 128);
+}
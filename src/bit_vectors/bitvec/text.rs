@@ -0,0 +1,128 @@
+//! Conversions between bit vectors and text.
+//!
+//! Besides the `[1, 0, ...]` form of `Display`, a bit vector can be formatted as:
+//! - `{:b}`: the bits packed in index order, e.g. `0110`.
+//! - `{:#b}`: a dump of the words, each one written MSB-first and padded to the word width.
+//! - `{:x}`: the same dump of the words, but in hexadecimal. `{:#x}` prefixes every word with `0x`.
+//!
+//! Both the `Display` and the packed binary forms can be parsed back with `str::parse`.
+
+use std::error::Error;
+use std::fmt::{Binary, Display, Formatter, LowerHex};
+use std::str::FromStr;
+
+use super::BitVec;
+use crate::util::Word;
+
+/// Error returned when parsing a bit vector from a string that contains a character
+/// that is neither a bit nor a separator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBitVecError {
+    index: usize,
+    found: char,
+}
+
+impl ParseBitVecError {
+    /// Byte index of the invalid character in the parsed string.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The invalid character.
+    #[inline]
+    pub fn found(&self) -> char {
+        self.found
+    }
+
+    /// Moves the index by `offset`, for errors in a part of a larger string.
+    pub(crate) fn shifted(mut self, offset: usize) -> Self {
+        self.index += offset;
+        self
+    }
+}
+
+impl Display for ParseBitVecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid character {:?} at index {}, expected '0', '1' or a separator",
+            self.found, self.index
+        )
+    }
+}
+
+impl Error for ParseBitVecError {}
+
+impl<W: Word> FromStr for BitVec<W> {
+    type Err = ParseBitVecError;
+
+    /// Parses a string of `0`s and `1`s in index order. The bits can be separated by
+    /// `_`, `,` or whitespace, and the whole string can be enclosed in brackets,
+    /// so both `"0110_1001"` and `"[0, 1, 1, 0]"` are accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let (start, content) = match trimmed
+            .strip_prefix('[')
+            .and_then(|inner| inner.strip_suffix(']'))
+        {
+            Some(inner) => (s.len() - s.trim_start().len() + 1, inner),
+            None => (s.len() - s.trim_start().len(), trimmed),
+        };
+
+        let mut bitvec = Self::with_bit_capacity(content.len());
+        for (index, c) in content.char_indices() {
+            match c {
+                '0' => bitvec.push(false),
+                '1' => bitvec.push(true),
+                '_' | ',' => {}
+                c if c.is_whitespace() => {}
+                found => {
+                    return Err(ParseBitVecError {
+                        index: start + index,
+                        found,
+                    })
+                }
+            }
+        }
+        Ok(bitvec)
+    }
+}
+
+impl<W: Word> Binary for BitVec<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            let width = W::BITS;
+            return write_words(f, &self.raw_data, |f, word| write!(f, "{word:0width$b}"));
+        }
+        for bit in self.iter() {
+            write!(f, "{}", if bit { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Word> LowerHex for BitVec<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = W::BITS / 4;
+        let prefix = if f.alternate() { "0x" } else { "" };
+        write_words(f, &self.raw_data, |f, word| {
+            write!(f, "{prefix}{word:0width$x}")
+        })
+    }
+}
+
+/// Writes the words separated by spaces, formatting each one with `write_word`.
+fn write_words<W: Word>(
+    f: &mut Formatter<'_>,
+    words: &[W],
+    write_word: impl Fn(&mut Formatter<'_>, W) -> std::fmt::Result,
+) -> std::fmt::Result {
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        write_word(f, *word)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! Word-parallel transforms over the whole bit vector: shifts, rotations, reversal
+//! and range fills. None of them changes the length of the bit vector.
+//!
+//! Shifts and rotations follow the semantics of the integer operators, as bits are
+//! stored in LSB-first order: shifting left moves every bit to a greater index, the
+//! same way `<<` moves the bits of an integer towards its most significant bit.
+
+use std::cmp::{max, min};
+use std::ops::Range;
+
+use super::BitVec;
+use crate::util::{bitmask, Word};
+
+impl<W: Word> BitVec<W> {
+    /// Shifts the bits `n` positions to the left, so the bit at index `i` moves to `i + n`.
+    /// The last `n` bits are discarded and the first `n` bits are set to 0.
+    #[inline]
+    pub fn shift_left(&mut self, n: usize) {
+        if n >= self.len {
+            self.fill_range(0..self.len, false);
+            return;
+        }
+
+        let word_shift = n / W::BITS;
+        let bit_shift = n % W::BITS;
+
+        // Traverse the words backwards, so the words we read have not been overwritten yet
+        for i in (0..self.raw_data.len()).rev() {
+            self.raw_data[i] = if i < word_shift {
+                W::ZERO
+            } else if bit_shift == 0 || i == word_shift {
+                self.raw_data[i - word_shift] << bit_shift
+            } else {
+                (self.raw_data[i - word_shift] << bit_shift)
+                    | (self.raw_data[i - word_shift - 1] >> (W::BITS - bit_shift))
+            };
+        }
+        self.clear_unused_bits();
+    }
+
+    /// Shifts the bits `n` positions to the right, so the bit at index `i` moves to `i - n`.
+    /// The first `n` bits are discarded and the last `n` bits are set to 0.
+    #[inline]
+    pub fn shift_right(&mut self, n: usize) {
+        if n >= self.len {
+            self.fill_range(0..self.len, false);
+            return;
+        }
+
+        let word_shift = n / W::BITS;
+        let bit_shift = n % W::BITS;
+        let num_words = self.raw_data.len();
+
+        // Traverse the words forwards, so the words we read have not been overwritten yet.
+        // There are no dirty bits past len, so the bits that enter from the end are 0s.
+        for i in 0..num_words {
+            let source = i + word_shift;
+            self.raw_data[i] = if source >= num_words {
+                W::ZERO
+            } else if bit_shift == 0 || source + 1 == num_words {
+                self.raw_data[source] >> bit_shift
+            } else {
+                (self.raw_data[source] >> bit_shift)
+                    | (self.raw_data[source + 1] << (W::BITS - bit_shift))
+            };
+        }
+    }
+
+    /// Rotates the bits `n` positions to the left, so the bit at index `i` moves to
+    /// `(i + n) % len`.
+    #[inline]
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 || n.is_multiple_of(self.len) {
+            return;
+        }
+        let n = n % self.len;
+
+        // In place, with three reversals: the last n bits end up first, and both parts
+        // recover their order
+        self.reverse();
+        self.reverse_range(0..n);
+        self.reverse_range(n..self.len);
+    }
+
+    /// Rotates the bits `n` positions to the right, so the bit at index `i` moves to
+    /// `(i - n) % len`.
+    #[inline]
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        self.rotate_left(self.len - n % self.len);
+    }
+
+    /// Reverses the order of the bits, so the bit at index `i` moves to `len - 1 - i`.
+    #[inline]
+    pub fn reverse(&mut self) {
+        self.raw_data.reverse();
+        for word in self.raw_data.iter_mut() {
+            *word = word.reverse_bits();
+        }
+
+        // The bits are now aligned to the end of the last word, so move them to the start
+        let num_bits = self.raw_data.len() * W::BITS;
+        let padding = num_bits - self.len;
+        if padding != 0 {
+            let len = self.len;
+            self.len = num_bits;
+            self.shift_right(padding);
+            self.len = len;
+        }
+    }
+
+    /// Reverses the order of the bits in `range`, swapping a word of bits from each end
+    /// at a time.
+    fn reverse_range(&mut self, range: Range<usize>) {
+        let mut start = range.start;
+        let mut end = range.end;
+        while end - start > 1 {
+            let width = min(W::BITS, (end - start) / 2);
+            let first = self.read_bits(start, width).reverse_bits() >> (W::BITS - width);
+            let last = self.read_bits(end - width, width).reverse_bits() >> (W::BITS - width);
+            self.set_bits(start..start + width, last);
+            self.set_bits(end - width..end, first);
+            start += width;
+            end -= width;
+        }
+    }
+
+    /// Sets all the bits in `range` to `value`. Unlike `set_bits`, the range can be of
+    /// any length, as the bits are written a word at a time.
+    #[inline]
+    pub fn fill_range(&mut self, range: Range<usize>, value: bool) {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "Cannot fill range {range:?} of a BitVec of length {}",
+            self.len
+        );
+
+        if range.is_empty() {
+            return;
+        }
+
+        let value = if value { W::MAX } else { W::ZERO };
+        let first_word = range.start / W::BITS;
+        let last_word = (range.end - 1) / W::BITS;
+        for i in first_word..=last_word {
+            let word_start = i * W::BITS;
+            let start = max(range.start, word_start) - word_start;
+            let end = min(range.end, word_start + W::BITS) - word_start;
+
+            let mask = bitmask!(W, end - start, start);
+            let word = &mut self.raw_data[i];
+            *word = (*word & !mask) | (value & mask);
+        }
+    }
+}
@@ -1,12 +1,13 @@
 pub mod bitvec;
 pub mod rank_select;
+pub mod rank_select_bitvec;
 pub mod rrr_bitvec;
+pub mod sd_vec;
 
-// TODO: not implemented yet.
-// pub mod sd_vec;
-
-pub use crate::bit_vectors::bitvec::BitVec;
-pub use crate::bit_vectors::rrr_bitvec::RRRBitVec;
+pub use crate::bit_vectors::bitvec::{BitSlice, BitVec, ParseBitVecError};
+pub use crate::bit_vectors::rank_select_bitvec::RankSelectBitVec;
+pub use crate::bit_vectors::rrr_bitvec::{ParseRRRBitVecError, RRRBitVec, RRRBitVecBuilder};
+pub use crate::bit_vectors::sd_vec::SDVec;
 
 pub trait Access {
     // Trait that defines access operation.
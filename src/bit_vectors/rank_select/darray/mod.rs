@@ -0,0 +1,247 @@
+//! Constant-time select data structure, as described by Okanohara and Sadakane in
+//! "Practical Entropy-Compressed Rank/Select Dictionary" (darray).
+//!
+//! The 1s (or 0s) are split into blocks of 1024. If a block spans at least 2^16 bits, it is
+//! sparse and the positions of all of its bits are stored explicitly. Otherwise, the position
+//! of its first bit is stored, along with the offset to it of every 32-th bit, so a select
+//! query only needs to scan the words between a sampled bit and the target bit.
+
+use super::{SelectStructure, SelectSupport};
+use crate::Build;
+use crate::{
+    bit_vectors::{bitvec::iter::IterBits, BitVec},
+    profiling::HeapSize,
+    util::{bitmask, select_in_word, Word},
+};
+use serde::{Deserialize, Serialize};
+
+const BLOCK_LEN: usize = 1024;
+const SUBBLOCK_LEN: usize = 32;
+const MAX_DENSE_BLOCK_SPAN: usize = 1 << 16;
+
+/// Select index over the 1s of a bit vector if `VALUE` is true, or over its 0s otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DArrayIndex<const VALUE: bool> {
+    /// Position of the first bit of every dense block, or -(i + 1) for sparse blocks,
+    /// where `i` is the index in `overflow_positions` of the first bit of the block.
+    blocks: Vec<i64>,
+    /// Offset of every 32-th bit from the start of its block. Sparse blocks hold
+    /// placeholders, so the index of a subblock is always `rank / 32`.
+    subblocks: Vec<u16>,
+    overflow_positions: Vec<usize>,
+    len: usize,
+}
+
+/// DArray over the 1s, which supports select in constant time.
+pub type DArray1 = DArrayIndex<true>;
+/// DArray over the 0s, which supports select0 in constant time.
+pub type DArray0 = DArrayIndex<false>;
+
+impl<const VALUE: bool> DArrayIndex<VALUE> {
+    pub fn new<W: Word>(data: &BitVec<W>) -> Self {
+        let mut blocks = Vec::new();
+        let mut subblocks = Vec::new();
+        let mut overflow_positions = Vec::new();
+        let mut len = 0;
+
+        let mut block = Vec::with_capacity(BLOCK_LEN);
+        let mut positions = IterBits::<W, VALUE>::new(data, 0..data.len()).peekable();
+        while positions.peek().is_some() {
+            block.clear();
+            block.extend(positions.by_ref().take(BLOCK_LEN));
+            len += block.len();
+
+            let first = block[0];
+            let last = block[block.len() - 1];
+            if last - first < MAX_DENSE_BLOCK_SPAN {
+                blocks.push(first as i64);
+                subblocks.extend(
+                    block
+                        .iter()
+                        .step_by(SUBBLOCK_LEN)
+                        .map(|position| (position - first) as u16),
+                );
+            } else {
+                blocks.push(-(overflow_positions.len() as i64) - 1);
+                subblocks.extend(std::iter::repeat_n(
+                    u16::MAX,
+                    block.len().div_ceil(SUBBLOCK_LEN),
+                ));
+                overflow_positions.extend_from_slice(&block);
+            }
+        }
+
+        Self {
+            blocks,
+            subblocks,
+            overflow_positions,
+            len,
+        }
+    }
+
+    /// Number of bits indexed, i.e. the number of 1s if `VALUE` is true, or of 0s otherwise.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the position after the bit with rank `rank` of the indexed value, or None
+    /// if there is no such bit. By definition, select(0) = 0.
+    ///
+    /// # Safety
+    /// The data used must be the same data that the structure was built with.
+    #[inline]
+    pub unsafe fn select<W: Word>(&self, data: &BitVec<W>, rank: usize) -> Option<usize> {
+        if rank == 0 {
+            return Some(0);
+        }
+        if rank > self.len {
+            return None;
+        }
+
+        let index = rank - 1;
+        let block = *self.blocks.get_unchecked(index / BLOCK_LEN);
+        if block < 0 {
+            let first = (-block - 1) as usize;
+            let position = *self
+                .overflow_positions
+                .get_unchecked(first + index % BLOCK_LEN);
+            return Some(position + 1);
+        }
+
+        let start = block as usize + *self.subblocks.get_unchecked(index / SUBBLOCK_LEN) as usize;
+        Some(Self::select_from(data, start, index % SUBBLOCK_LEN))
+    }
+
+    /// Returns the position after the `remaining`-th (0-based) bit of the indexed value
+    /// at a position greater or equal than `start`.
+    ///
+    /// # Safety
+    /// The bit must exist.
+    #[inline]
+    unsafe fn select_from<W: Word>(data: &BitVec<W>, start: usize, mut remaining: usize) -> usize {
+        let raw_data = data.raw_data();
+        let mut word_index = start / W::BITS;
+        let offset = start % W::BITS;
+        let read_word = |word_index: usize| {
+            let word = *raw_data.get_unchecked(word_index);
+            if VALUE {
+                word
+            } else {
+                !word
+            }
+        };
+
+        // Discard the bits before start
+        let mut word = read_word(word_index) & !bitmask!(W, offset, 0);
+        loop {
+            let count = word.count_ones() as usize;
+            if remaining < count {
+                return word_index * W::BITS + select_in_word(word, remaining) + 1;
+            }
+            remaining -= count;
+            word_index += 1;
+            word = read_word(word_index);
+        }
+    }
+}
+
+impl<const VALUE: bool> HeapSize for DArrayIndex<VALUE> {
+    #[inline]
+    fn heap_size_in_bits(&self) -> usize {
+        self.blocks.heap_size_in_bits()
+            + self.subblocks.heap_size_in_bits()
+            + self.overflow_positions.heap_size_in_bits()
+    }
+}
+
+/// Select support with a darray over the 1s and another one over the 0s, so both select
+/// and select0 run in constant time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DArray {
+    ones: DArray1,
+    zeros: DArray0,
+}
+
+impl DArray {
+    pub fn new<W: Word>(data: &BitVec<W>) -> Self {
+        Self {
+            ones: DArray1::new(data),
+            zeros: DArray0::new(data),
+        }
+    }
+
+    #[inline]
+    pub fn ones(&self) -> &DArray1 {
+        &self.ones
+    }
+
+    #[inline]
+    pub fn zeros(&self) -> &DArray0 {
+        &self.zeros
+    }
+}
+
+impl HeapSize for DArray {
+    #[inline]
+    fn heap_size_in_bits(&self) -> usize {
+        self.ones.heap_size_in_bits() + self.zeros.heap_size_in_bits()
+    }
+}
+
+impl<W: Word> SelectSupport<BitVec<W>> for DArray {
+    #[inline]
+    unsafe fn select(&self, data: &BitVec<W>, rank: usize) -> Option<usize> {
+        self.ones.select(data, rank)
+    }
+
+    #[inline]
+    unsafe fn select0(&self, data: &BitVec<W>, rank0: usize) -> Option<usize> {
+        self.zeros.select(data, rank0)
+    }
+}
+
+pub struct DArraySpec;
+
+impl DArraySpec {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for DArraySpec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DArray {
+    #[inline]
+    pub const fn spec() -> DArraySpec {
+        DArraySpec::new()
+    }
+}
+
+impl<W: Word> Build<BitVec<W>, SelectStructure<BitVec<W>, DArray>> for DArraySpec {
+    #[inline]
+    fn build(&self, data: BitVec<W>) -> SelectStructure<BitVec<W>, DArray> {
+        let darray = DArray::new(&data);
+        unsafe { SelectStructure::new(data, darray) }
+    }
+}
+
+impl<W: Word> Build<&BitVec<W>, DArray> for DArraySpec {
+    #[inline]
+    fn build(&self, data: &BitVec<W>) -> DArray {
+        DArray::new(data)
+    }
+}
+
+#[cfg(test)]
+mod tests;
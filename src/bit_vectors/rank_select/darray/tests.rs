@@ -0,0 +1,114 @@
+use super::*;
+use crate::bit_vectors::rank_select::tests_utils::{
+    assert_rank, assert_select, assert_select0, test_access_for, test_for_words, test_select_for,
+};
+use crate::bit_vectors::rank_select::{DenseSamplingRank, RankSelectStructure, Select};
+
+#[test]
+fn new() {
+    // Dense blocks: 1 one in every byte
+    let bv = BitVec::from([0b00100000u8; 3000]);
+    let darray = DArray1::new(&bv);
+
+    assert_eq!(darray.len(), 3000);
+    assert_eq!(darray.blocks, [5, 5 + 1024 * 8, 5 + 2048 * 8]);
+    assert_eq!(darray.subblocks.len(), 3000usize.div_ceil(32));
+    assert_eq!(darray.subblocks[0..3], [0, 32 * 8, 64 * 8]);
+    assert!(darray.overflow_positions.is_empty());
+}
+
+#[test]
+fn new_when_blocks_are_sparse() {
+    // 1 one every 128 bits, so the first block of 1024 ones spans more than 2^16 bits,
+    // but the last one, with 476 ones, does not
+    let bv = BitVec::from([1u128; 1500]);
+    let darray = DArray1::new(&bv);
+
+    assert_eq!(darray.blocks, [-1, 1024 * 128]);
+    assert_eq!(darray.subblocks[0..32], [u16::MAX; 32]);
+    assert_eq!(darray.subblocks[32..34], [0, 32 * 128]);
+    assert_eq!(
+        darray.overflow_positions,
+        (0..1024).map(|i| i * 128).collect::<Vec<_>>()
+    );
+
+    // The 0s are dense
+    let darray = DArray0::new(&bv);
+    assert_eq!(darray.len(), 1500 * 127);
+    assert!(darray.blocks.iter().all(|block| *block >= 0));
+    assert!(darray.overflow_positions.is_empty());
+}
+
+#[test]
+fn new_when_empty_bitvec() {
+    let bv: BitVec = BitVec::new();
+    let darray = DArray::new(&bv);
+
+    assert!(darray.ones().is_empty());
+    assert!(darray.zeros().is_empty());
+    assert_eq!(darray.heap_size_in_bits(), 0);
+}
+
+#[test]
+fn select_when_blocks_are_sparse_and_dense() {
+    // A dense run of 1s followed by sparse 1s
+    let mut bv: BitVec = BitVec::from_value(true, 5000);
+    for _ in 0..3000 {
+        bv.push(true);
+        bv.extend(std::iter::repeat_n(false, 99));
+    }
+    let rs = DArray::spec().build(bv.clone());
+    assert!(!rs.select_support().ones().overflow_positions.is_empty());
+
+    for (i, position) in bv.iter_ones().enumerate() {
+        assert_eq!(rs.select(i + 1), Some(position + 1));
+    }
+    for (i, position) in bv.iter_zeros().enumerate() {
+        assert_eq!(rs.select0(i + 1), Some(position + 1));
+    }
+}
+
+#[test]
+fn build_rank_select_structure() {
+    let bv = (0..20_000)
+        .map(|_| rand::random::<bool>())
+        .collect::<BitVec>();
+    let rs: RankSelectStructure<BitVec, DenseSamplingRank, DArray> =
+        DenseSamplingRank::spec(4).build(DArray::spec().build(bv.clone()));
+
+    assert_rank(&bv, &rs);
+    assert_select(&bv, &rs);
+}
+
+test_select_for!(DArray,);
+test_access_for!(DArray,);
+
+#[test]
+fn select_when_block_span_is_around_2_16() {
+    // The 1024 1s of the first block span 2^16 - 1 bits, the maximum for a dense block,
+    // or 2^16 bits
+    for (last, is_sparse) in [
+        (MAX_DENSE_BLOCK_SPAN - 1, false),
+        (MAX_DENSE_BLOCK_SPAN, true),
+    ] {
+        let mut bv: BitVec = BitVec::from_value(true, BLOCK_LEN - 1);
+        bv.extend(std::iter::repeat_n(false, last - bv.len()));
+        bv.extend([true, false, true]);
+        let rs = DArray::spec().build(bv.clone());
+
+        let ones = rs.select_support().ones();
+        assert_eq!(ones.blocks[0] < 0, is_sparse);
+        assert_eq!(
+            ones.overflow_positions.len(),
+            is_sparse as usize * BLOCK_LEN
+        );
+        assert_select(&bv, &rs);
+        assert_select0(&bv, &rs);
+    }
+}
+
+test_for_words!(select, |bv| {
+    let rs = DArray::spec().build(bv.clone());
+    assert_select(&bv, &rs);
+    assert_select0(&bv, &rs);
+});
@@ -0,0 +1,347 @@
+//! Rank/Select data structure over the occurrences of a bit pattern of 2 to 4 bits, such
+//! as `10` or `01`, which are useful for balanced parentheses and runs of bits.
+//!
+//! As in DenseSamplingRank, every superblock of `k` words stores the number of occurrences
+//! before it, and every word stores the number of occurrences before it relative to its
+//! superblock. An occurrence belongs to the word of its last bit, so occurrences that cross
+//! a word boundary are counted once. The occurrences in a word are found with shifts and
+//! masks over the word and the one before it.
+
+use super::{CounterWidth, PatternRankStructure, PatternRankSupport, SuperblockCounters};
+use crate::Build;
+use crate::{
+    bit_vectors::BitVec,
+    int_vectors::CompactIntVec,
+    profiling::HeapSize,
+    util::{bitmask, select_in_word, BitsRequired, Word},
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::min;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const MIN_PATTERN_LEN: usize = 2;
+const MAX_PATTERN_LEN: usize = 4;
+
+/// Pattern of 2 to 4 bits, where the bit `i` of `bits` is the `i`-th bit of the pattern.
+/// It is written, and parsed, with its first bit as the first character, so `10` is a 1
+/// followed by a 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "SerializedBitPattern")]
+pub struct BitPattern {
+    bits: u8,
+    len: usize,
+}
+
+#[derive(Deserialize)]
+struct SerializedBitPattern {
+    bits: u8,
+    len: usize,
+}
+
+/// Error returned when a [BitPattern] is not valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitPatternError {
+    /// The pattern has not between 2 and 4 bits.
+    InvalidLength(usize),
+    /// There are bits set after the length of the pattern.
+    BitsOutOfRange { bits: u8, len: usize },
+    /// A character of the parsed string, at the given byte index, is not `0` or `1`.
+    InvalidCharacter { index: usize, found: char },
+}
+
+impl Display for BitPatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(
+                f,
+                "the pattern must have between {MIN_PATTERN_LEN} and {MAX_PATTERN_LEN} bits, got {len}"
+            ),
+            Self::BitsOutOfRange { bits, len } => {
+                write!(f, "the bits {bits:#b} do not fit in a pattern of {len} bits")
+            }
+            Self::InvalidCharacter { index, found } => {
+                write!(f, "invalid character {found:?} at index {index}, expected 0 or 1")
+            }
+        }
+    }
+}
+
+impl Error for BitPatternError {}
+
+impl BitPattern {
+    /// Creates the pattern of the `len` lower bits of `bits`, where the least significant
+    /// bit is the first bit of the pattern.
+    pub const fn new(bits: u8, len: usize) -> Result<Self, BitPatternError> {
+        if len < MIN_PATTERN_LEN || len > MAX_PATTERN_LEN {
+            return Err(BitPatternError::InvalidLength(len));
+        }
+        if bits >> len != 0 {
+            return Err(BitPatternError::BitsOutOfRange { bits, len });
+        }
+        Ok(Self { bits, len })
+    }
+
+    /// Bits of the pattern, with its first bit as the least significant one.
+    #[inline]
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Number of bits of the pattern.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a word with a 1 at every bit of `word` where an occurrence of the pattern
+    /// ends, where `previous` is the word before `word` in the bit vector.
+    #[inline]
+    fn matches<W: Word>(&self, previous: W, word: W) -> W {
+        let mut matches = !W::ZERO;
+        for t in 0..self.len {
+            // The bit b of shifted is the bit b - t of the bit vector
+            let shifted = if t == 0 {
+                word
+            } else {
+                (word << t) | (previous >> (W::BITS - t))
+            };
+            // which must be the bit len - 1 - t of the pattern
+            if (self.bits >> (self.len - 1 - t)) & 1 == 1 {
+                matches &= shifted;
+            } else {
+                matches &= !shifted;
+            }
+        }
+        matches
+    }
+
+    /// Returns the occurrences of the pattern that end in the word `index` of `data`,
+    /// including the ones that end after its length.
+    #[inline]
+    fn word_matches<W: Word>(&self, data: &BitVec<W>, index: usize) -> W {
+        let raw_data = data.raw_data();
+        let word = raw_data.get(index).copied().unwrap_or(W::ZERO);
+        if index == 0 {
+            // There are no bits before the first word, so the pattern cannot end
+            // before its len - 1 bit
+            return self.matches(W::ZERO, word) & !bitmask!(W, self.len - 1, 0);
+        }
+        self.matches(raw_data[index - 1], word)
+    }
+}
+
+impl TryFrom<SerializedBitPattern> for BitPattern {
+    type Error = BitPatternError;
+
+    fn try_from(pattern: SerializedBitPattern) -> Result<Self, Self::Error> {
+        Self::new(pattern.bits, pattern.len)
+    }
+}
+
+impl FromStr for BitPattern {
+    type Err = BitPatternError;
+
+    /// Parses a pattern of `0`s and `1`s, with its first bit as the first character.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bits = 0u8;
+        let mut len = 0;
+        for (index, c) in s.char_indices() {
+            match c {
+                '0' => {}
+                '1' if len < MAX_PATTERN_LEN => bits |= 1 << len,
+                '1' => {}
+                found => return Err(BitPatternError::InvalidCharacter { index, found }),
+            }
+            len += 1;
+        }
+        Self::new(bits, len)
+    }
+}
+
+impl Display for BitPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.len {
+            write!(f, "{}", (self.bits >> i) & 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DenseSamplingPatternRank {
+    pattern: BitPattern,
+    superblocks: SuperblockCounters,
+    blocks: CompactIntVec,
+    k: usize,
+    total_rank: usize,
+}
+
+impl DenseSamplingPatternRank {
+    /// # Panics
+    /// If `k` is 0.
+    pub fn new<W: Word>(data: &BitVec<W>, pattern: BitPattern, k: usize) -> Self {
+        assert!(k > 0, "k must be greater than 0");
+
+        let n = data.len();
+        // There is a block for index == n
+        let num_blocks = n / W::BITS + 1;
+        let mut superblocks =
+            SuperblockCounters::with_capacity(CounterWidth::Auto, n, num_blocks.div_ceil(k));
+        let rank_offset_int_width = ((k - 1) * W::BITS).bits_required() as usize;
+        let mut blocks = CompactIntVec::with_capacity(rank_offset_int_width, num_blocks);
+
+        let mut rank = 0;
+        let mut rank_offset = 0;
+        for i in 0..num_blocks {
+            if i % k == 0 {
+                superblocks.push(rank);
+                rank_offset = 0;
+            }
+            blocks.push(rank_offset);
+
+            // Discard the occurrences that end after the last bit
+            let word_len = min(n - min(i * W::BITS, n), W::BITS);
+            let matches = pattern.word_matches(data, i) & bitmask!(W, word_len, 0);
+            let count = matches.count_ones() as usize;
+            rank += count;
+            rank_offset += count;
+        }
+
+        Self {
+            pattern,
+            superblocks,
+            blocks,
+            k,
+            total_rank: rank,
+        }
+    }
+
+    #[inline]
+    pub fn pattern(&self) -> BitPattern {
+        self.pattern
+    }
+
+    /// Number of occurrences of the pattern in the bit vector.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.total_rank
+    }
+
+    #[inline]
+    pub fn superblocks(&self) -> &SuperblockCounters {
+        &self.superblocks
+    }
+
+    #[inline]
+    pub fn blocks(&self) -> &CompactIntVec {
+        &self.blocks
+    }
+
+    #[inline]
+    pub fn k(&self) -> usize {
+        self.k
+    }
+}
+
+impl HeapSize for DenseSamplingPatternRank {
+    #[inline]
+    fn heap_size_in_bits(&self) -> usize {
+        self.superblocks.heap_size_in_bits() + self.blocks.heap_size_in_bits()
+    }
+}
+
+impl<W: Word> PatternRankSupport<BitVec<W>> for DenseSamplingPatternRank {
+    #[inline]
+    unsafe fn rank_pattern(&self, data: &BitVec<W>, index: usize) -> Option<usize> {
+        if index > data.len() {
+            return None;
+        }
+
+        let iw = index / W::BITS;
+        let rank = self.superblocks.get_unchecked(iw / self.k) + self.blocks.get_unchecked(iw);
+        let matches = self.pattern.word_matches(data, iw) & bitmask!(W, index % W::BITS, 0);
+        Some(rank + matches.count_ones() as usize)
+    }
+
+    #[inline]
+    unsafe fn select_pattern(&self, data: &BitVec<W>, rank: usize) -> Option<usize> {
+        // By definition, select_pattern(0) = 0
+        if rank == 0 {
+            return Some(0);
+        }
+        if rank > self.total_rank {
+            return None;
+        }
+
+        // Find the last superblock with less than `rank` occurrences before it. There is
+        // always one, as the first superblock has 0 occurrences before it.
+        let mut left = 0;
+        let mut right = self.superblocks.len() - 1;
+        while left < right {
+            let mid = (left + right).div_ceil(2);
+            if self.superblocks.get_unchecked(mid) < rank {
+                left = mid;
+            } else {
+                right = mid - 1;
+            }
+        }
+        let remaining_rank = rank - self.superblocks.get_unchecked(left);
+
+        // Same for the blocks of the superblock
+        let mut left_block = left * self.k;
+        let mut right_block = min(left_block + self.k, self.blocks.len()) - 1;
+        while left_block < right_block {
+            let mid = (left_block + right_block).div_ceil(2);
+            if self.blocks.get_unchecked(mid) < remaining_rank {
+                left_block = mid;
+            } else {
+                right_block = mid - 1;
+            }
+        }
+        let local_rank = remaining_rank - self.blocks.get_unchecked(left_block);
+
+        let matches = self.pattern.word_matches(data, left_block);
+        Some(left_block * W::BITS + select_in_word(matches, local_rank - 1) + 1)
+    }
+}
+
+pub struct DenseSamplingPatternRankSpec {
+    pub pattern: BitPattern,
+    pub k: usize,
+}
+
+impl DenseSamplingPatternRankSpec {
+    #[inline]
+    pub const fn new(pattern: BitPattern, k: usize) -> Self {
+        Self { pattern, k }
+    }
+}
+
+impl DenseSamplingPatternRank {
+    #[inline]
+    pub const fn spec(pattern: BitPattern, k: usize) -> DenseSamplingPatternRankSpec {
+        DenseSamplingPatternRankSpec::new(pattern, k)
+    }
+}
+
+impl<W: Word> Build<BitVec<W>, PatternRankStructure<BitVec<W>, DenseSamplingPatternRank>>
+    for DenseSamplingPatternRankSpec
+{
+    #[inline]
+    fn build(&self, data: BitVec<W>) -> PatternRankStructure<BitVec<W>, DenseSamplingPatternRank> {
+        let pattern_rank = DenseSamplingPatternRank::new(&data, self.pattern, self.k);
+        unsafe { PatternRankStructure::new(data, pattern_rank) }
+    }
+}
+
+impl<W: Word> Build<&BitVec<W>, DenseSamplingPatternRank> for DenseSamplingPatternRankSpec {
+    #[inline]
+    fn build(&self, data: &BitVec<W>) -> DenseSamplingPatternRank {
+        DenseSamplingPatternRank::new(data, self.pattern, self.k)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,174 @@
+use crate::bit_vectors::rank_select::tests_utils::test_for_words;
+use crate::bit_vectors::rank_select::PatternRank;
+use crate::bit_vectors::{Access, BitVec};
+
+use super::*;
+
+fn pattern(s: &str) -> BitPattern {
+    s.parse().unwrap()
+}
+
+/// Returns the position after the last bit of every occurrence of `pattern` in `bv`.
+fn occurrence_ends<W: Word>(bv: &BitVec<W>, pattern: &str) -> Vec<usize> {
+    let pattern = pattern.chars().map(|c| c == '1').collect::<Vec<_>>();
+    (pattern.len()..=bv.len())
+        .filter(|&end| {
+            let start = end - pattern.len();
+            pattern
+                .iter()
+                .enumerate()
+                .all(|(i, &bit)| bv.access(start + i) == Some(bit))
+        })
+        .collect()
+}
+
+#[test]
+fn new() {
+    // 1 0 1 1 0 0 1 0
+    let bv: BitVec = "10110010".parse().unwrap();
+    let rs = DenseSamplingPatternRank::spec(pattern("10"), 1).build(bv);
+
+    assert_eq!(rs.pattern_rank_support().pattern(), pattern("10"));
+    assert_eq!(rs.pattern_rank_support().count(), 3);
+    let ranks = (0..=8)
+        .map(|i| rs.rank_pattern(i).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ranks, [0, 0, 1, 1, 1, 2, 2, 2, 3]);
+    assert_eq!(rs.rank_pattern(9), None);
+
+    assert_eq!(rs.select_pattern(0), Some(0));
+    assert_eq!(rs.select_pattern(1), Some(2));
+    assert_eq!(rs.select_pattern(2), Some(5));
+    assert_eq!(rs.select_pattern(3), Some(8));
+    assert_eq!(rs.select_pattern(4), None);
+}
+
+#[test]
+fn new_when_pattern_starts_with_0s() {
+    // A pattern of 0s must not match before the first bit or after the last one
+    let bv: BitVec = BitVec::from_value(false, 100);
+    let rs = DenseSamplingPatternRank::spec(pattern("000"), 2).build(bv);
+
+    assert_eq!(rs.pattern_rank_support().count(), 98);
+    assert_eq!(rs.rank_pattern(2), Some(0));
+    assert_eq!(rs.rank_pattern(3), Some(1));
+    assert_eq!(rs.select_pattern(1), Some(3));
+    assert_eq!(rs.select_pattern(98), Some(100));
+    assert_eq!(rs.select_pattern(99), None);
+}
+
+#[test]
+fn new_when_empty() {
+    let bv: BitVec = BitVec::new();
+    let rs = DenseSamplingPatternRank::spec(pattern("01"), 4).build(bv);
+
+    assert_eq!(rs.pattern_rank_support().count(), 0);
+    assert_eq!(rs.rank_pattern(0), Some(0));
+    assert_eq!(rs.rank_pattern(1), None);
+    assert_eq!(rs.select_pattern(0), Some(0));
+    assert_eq!(rs.select_pattern(1), None);
+}
+
+#[test]
+fn heap_size_in_bits() {
+    let bv: BitVec = BitVec::from_value(true, 64 * 100);
+    let rs: DenseSamplingPatternRank = DenseSamplingPatternRank::spec(pattern("11"), 4).build(&bv);
+
+    assert_eq!(
+        rs.heap_size_in_bits(),
+        rs.superblocks().heap_size_in_bits() + rs.blocks().heap_size_in_bits()
+    );
+    // One 13-bit superblock every 4 words, in a CompactIntVec of whole words
+    assert_eq!(
+        rs.superblocks().heap_size_in_bits(),
+        ((bv.raw_data().len() + 1).div_ceil(4) * 13).div_ceil(64) * 64
+    );
+}
+
+#[test]
+fn bit_pattern_new() {
+    let pattern = BitPattern::new(0b001, 3).unwrap();
+    assert_eq!(pattern.bits(), 0b001);
+    assert_eq!(pattern.width(), 3);
+    assert_eq!(pattern.to_string(), "100");
+    assert_eq!(pattern, "100".parse().unwrap());
+
+    assert_eq!(
+        BitPattern::new(0b1, 1),
+        Err(BitPatternError::InvalidLength(1))
+    );
+    assert_eq!(
+        BitPattern::new(0, 5),
+        Err(BitPatternError::InvalidLength(5))
+    );
+    assert_eq!(
+        BitPattern::new(0b100, 2),
+        Err(BitPatternError::BitsOutOfRange {
+            bits: 0b100,
+            len: 2
+        })
+    );
+}
+
+#[test]
+fn bit_pattern_from_str() {
+    for s in ["00", "01", "10", "11", "101", "0110", "1111"] {
+        assert_eq!(pattern(s).to_string(), s);
+    }
+
+    assert_eq!(
+        "1".parse::<BitPattern>(),
+        Err(BitPatternError::InvalidLength(1))
+    );
+    assert_eq!(
+        "10101".parse::<BitPattern>(),
+        Err(BitPatternError::InvalidLength(5))
+    );
+    assert_eq!(
+        "1a".parse::<BitPattern>(),
+        Err(BitPatternError::InvalidCharacter {
+            index: 1,
+            found: 'a'
+        })
+    );
+}
+
+#[test]
+fn bit_pattern_deserialize_checks_the_pattern() {
+    let pattern = pattern("110");
+    let json = serde_json::to_string(&pattern).unwrap();
+    assert_eq!(serde_json::from_str::<BitPattern>(&json).unwrap(), pattern);
+
+    assert!(serde_json::from_str::<BitPattern>(r#"{"bits":8,"len":3}"#).is_err());
+    assert!(serde_json::from_str::<BitPattern>(r#"{"bits":0,"len":5}"#).is_err());
+}
+
+/// Checks rank_pattern and select_pattern of `rs` at every position and occurrence,
+/// where `expected` are the ends of the occurrences of the pattern in `bv`.
+fn assert_pattern_rank<W: Word>(bv: &BitVec<W>, rs: &impl PatternRank, expected: &[usize]) {
+    let mut rank = 0;
+    for i in 0..=bv.len() {
+        while rank < expected.len() && expected[rank] <= i {
+            rank += 1;
+        }
+        assert_eq!(rs.rank_pattern(i), Some(rank), "rank_pattern({i})");
+    }
+    assert_eq!(rs.rank_pattern(bv.len() + 1), None);
+
+    assert_eq!(rs.select_pattern(0), Some(0));
+    for (rank, end) in expected.iter().enumerate() {
+        assert_eq!(rs.select_pattern(rank + 1), Some(*end));
+    }
+    assert_eq!(rs.select_pattern(expected.len() + 1), None);
+}
+
+test_for_words!(rank_and_select_pattern, |bv| {
+    for pattern in ["10", "01", "00", "11", "101", "110", "0110", "1111", "0000"] {
+        let expected = occurrence_ends(&bv, pattern);
+        for k in [1, 3, 8] {
+            let rs = DenseSamplingPatternRank::spec(self::pattern(pattern), k).build(bv.clone());
+            assert_eq!(rs.pattern_rank_support().count(), expected.len());
+            assert_pattern_rank(&bv, &rs, &expected);
+        }
+    }
+});
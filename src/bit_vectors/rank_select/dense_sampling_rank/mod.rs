@@ -1,36 +1,52 @@
-use super::{RankStructure, RankSupport, SelectSupport};
+use super::{CounterWidth, RankStructure, RankSupport, SelectSupport, SuperblockCounters};
 use crate::Build;
 use crate::{
-    bit_vectors::BitVec, int_vectors::compact_int_vec::CompactIntVec, profiling::HeapSize,
-    util::BitsRequired,
+    bit_vectors::BitVec,
+    int_vectors::compact_int_vec::CompactIntVec,
+    profiling::HeapSize,
+    util::{bitmask, select_in_word, BitsRequired, Word},
 };
 use serde::{Deserialize, Serialize};
 use std::cmp::min;
 
-///
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DenseSamplingRank {
-    superblocks: Vec<usize>,
+    superblocks: SuperblockCounters,
     superblock_size: usize,
     blocks: CompactIntVec,
     k: usize,
     total_rank: usize,
+    s: Option<usize>,
+    /// Superblock of every s-th 1, if select samples were requested.
+    select_samples: CompactIntVec,
+    /// Superblock of every s-th 0, if select samples were requested.
+    select0_samples: CompactIntVec,
 }
 
 impl DenseSamplingRank {
     #[inline]
-    pub fn new(data: &BitVec, k: usize) -> Self {
+    pub fn new<W: Word>(data: &BitVec<W>, k: usize) -> Self {
+        Self::new_with_counter_width(data, k, CounterWidth::Auto)
+    }
+
+    /// Builds the structure storing the superblock ranks with the given width.
+    pub fn new_with_counter_width<W: Word>(
+        data: &BitVec<W>,
+        k: usize,
+        counter_width: CounterWidth,
+    ) -> Self {
         assert!(k > 0, "k must be greater than 0");
 
-        let superblock_size = k * BitVec::CONTAINER_WIDTH;
+        let superblock_size = k * W::BITS;
         let num_superblocks = data.len() / superblock_size;
-        let mut superblocks = Vec::with_capacity(num_superblocks + 1);
+        let mut superblocks =
+            SuperblockCounters::with_capacity(counter_width, data.len(), num_superblocks + 1);
 
         // This could be superblock_size - block_size, but in practice it is enough with -1
-        let max_rank_offset_value = (k - 1) * BitVec::CONTAINER_WIDTH;
+        let max_rank_offset_value = (k - 1) * W::BITS;
         let rank_offset_int_width = max_rank_offset_value.bits_required() as usize;
 
-        let num_blocks = data.len() / BitVec::CONTAINER_WIDTH;
+        let num_blocks = data.len() / W::BITS;
         let mut blocks = CompactIntVec::with_capacity(rank_offset_int_width, num_blocks + 1);
 
         let mut rank = 0;
@@ -89,11 +105,46 @@ impl DenseSamplingRank {
             blocks,
             k,
             total_rank: rank,
+            s: None,
+            select_samples: CompactIntVec::new(1),
+            select0_samples: CompactIntVec::new(1),
         }
     }
 
+    /// Builds the structure along with the superblock of every `s`-th 1 and every `s`-th 0,
+    /// which bound the binary search over the superblocks on select and select0.
+    pub fn new_with_select_samples<W: Word>(data: &BitVec<W>, k: usize, s: usize) -> Self {
+        let mut rs = Self::new(data, k);
+        rs.sample_select(data, s);
+        rs
+    }
+
+    /// Samples the superblock of every `s`-th 1 and every `s`-th 0 of `data`, which must
+    /// be the data the structure was built with.
+    fn sample_select<W: Word>(&mut self, data: &BitVec<W>, s: usize) {
+        assert!(s > 0, "s must be greater than 0");
+
+        let width = self.superblocks.len().bits_required() as usize;
+        let superblock_size = self.superblock_size;
+
+        // Superblocks of the 1s and 0s with rank 1, s+1, 2s+1, ...
+        self.select_samples = CompactIntVec::new(width);
+        self.select_samples.extend(
+            data.iter_ones()
+                .step_by(s)
+                .map(|position| position / superblock_size),
+        );
+        self.select0_samples = CompactIntVec::new(width);
+        self.select0_samples.extend(
+            data.iter_zeros()
+                .step_by(s)
+                .map(|position| position / superblock_size),
+        );
+        self.s = Some(s);
+    }
+
     #[inline]
-    pub fn superblocks(&self) -> &[usize] {
+    pub fn superblocks(&self) -> &SuperblockCounters {
         &self.superblocks
     }
 
@@ -111,6 +162,44 @@ impl DenseSamplingRank {
     pub fn k(&self) -> usize {
         self.k
     }
+
+    /// Sampling rate of the select samples, or None if they were not built.
+    #[inline]
+    pub fn s(&self) -> Option<usize> {
+        self.s
+    }
+
+    #[inline]
+    pub fn select_samples(&self) -> &CompactIntVec {
+        &self.select_samples
+    }
+
+    #[inline]
+    pub fn select0_samples(&self) -> &CompactIntVec {
+        &self.select0_samples
+    }
+
+    /// Returns the superblocks between which the binary search for the bit with rank
+    /// `rank` must be done. Without select samples, these are the first and last ones.
+    ///
+    /// # Safety
+    /// There must be a bit with the given rank.
+    #[inline]
+    unsafe fn superblock_hints(&self, samples: &CompactIntVec, rank: usize) -> (usize, usize) {
+        let last = self.superblocks.len() - 1;
+        let Some(s) = self.s else {
+            return (0, last);
+        };
+
+        // The superblock of the sampled bit has less than `rank` bits before it, and the one
+        // after the superblock of the next sampled bit has at least `rank` bits before it
+        let p = (rank - 1) / s;
+        let left = samples.get_unchecked(p);
+        let right = samples
+            .get(p + 1)
+            .map_or(last, |superblock| min(superblock + 1, last));
+        (left, right)
+    }
 }
 impl HeapSize for DenseSamplingRank {
     #[inline]
@@ -118,13 +207,16 @@ impl HeapSize for DenseSamplingRank {
         // Do not rely on underlying data structure's heap_size_in_bits,
         // since we want to count the heap size of the rank structure without
         // static overhead (i.e first block/superblock that is pushed as a 0 value for convenience)
-        self.superblocks.heap_size_in_bits() + self.blocks.heap_size_in_bits()
+        self.superblocks.heap_size_in_bits()
+            + self.blocks.heap_size_in_bits()
+            + self.select_samples.heap_size_in_bits()
+            + self.select0_samples.heap_size_in_bits()
     }
 }
 
-impl RankSupport<BitVec> for DenseSamplingRank {
+impl<W: Word> RankSupport<BitVec<W>> for DenseSamplingRank {
     #[inline]
-    unsafe fn rank(&self, data: &BitVec, index: usize) -> Option<usize> {
+    unsafe fn rank(&self, data: &BitVec<W>, index: usize) -> Option<usize> {
         // By definition, rank(0) = 0
         if index == 0 {
             return Some(0);
@@ -134,23 +226,22 @@ impl RankSupport<BitVec> for DenseSamplingRank {
         }
 
         let is = index / self.superblock_size;
-        let iw = index / BitVec::CONTAINER_WIDTH;
+        let iw = index / W::BITS;
 
         let rank = self.superblocks.get_unchecked(is) + self.blocks.get_unchecked(iw);
-        let block_offset = index % BitVec::CONTAINER_WIDTH;
-        let last_block = data.raw_data().get(iw).copied().unwrap_or(0);
+        let block_offset = index % W::BITS;
+        let last_block = data.raw_data().get(iw).copied().unwrap_or(W::ZERO);
 
-        // getbits!(last_block, block_offset, 0) optimized, we know that block_offset is always <64 so this does not overflow
-        let last_block_target = last_block & ((1 << block_offset) - 1);
+        let last_block_target = last_block & bitmask!(W, block_offset, 0);
         let last_block_rank = last_block_target.count_ones() as usize;
 
         Some(rank + last_block_rank)
     }
 }
 
-impl SelectSupport<BitVec> for DenseSamplingRank {
+impl<W: Word> SelectSupport<BitVec<W>> for DenseSamplingRank {
     #[inline]
-    unsafe fn select(&self, data: &BitVec, rank: usize) -> Option<usize> {
+    unsafe fn select(&self, data: &BitVec<W>, rank: usize) -> Option<usize> {
         // By definition, select(0) = 0
         if rank == 0 {
             return Some(0);
@@ -160,12 +251,12 @@ impl SelectSupport<BitVec> for DenseSamplingRank {
             return None;
         }
 
-        let mut left_superblock = 0;
-        let mut right_superblock = self.superblocks.len() - 1;
+        let (mut left_superblock, mut right_superblock) =
+            self.superblock_hints(&self.select_samples, rank);
 
         while right_superblock - left_superblock > 1 {
             let mid_superblock = (left_superblock + right_superblock) / 2;
-            let mid_rank = *self.superblocks.get_unchecked(mid_superblock);
+            let mid_rank = self.superblocks.get_unchecked(mid_superblock);
             if mid_rank < rank {
                 left_superblock = mid_superblock;
             } else {
@@ -177,7 +268,7 @@ impl SelectSupport<BitVec> for DenseSamplingRank {
         // We know for sure that in left position is the superblock value that is the greatest
         // of the ranks <= rank, as the last position is always greater or equal than the rank,
         // as the total rank is the last superblock value.
-        let superblock_rank = *self.superblocks.get_unchecked(left_superblock);
+        let superblock_rank = self.superblocks.get_unchecked(left_superblock);
         let remaining_rank = rank - superblock_rank;
         let raw_data = data.raw_data();
         let mut left_block_index = left_superblock * self.k;
@@ -207,26 +298,19 @@ impl SelectSupport<BitVec> for DenseSamplingRank {
             left_block_index
         };
 
-        let mut local_rank = self.blocks.get_unchecked(target_block_index);
+        let local_rank = self.blocks.get_unchecked(target_block_index);
 
         // at this point, we are exactly that the block that contains the rank is `target_block_index`
-        let mut block = *raw_data.get_unchecked(target_block_index);
+        let block = *raw_data.get_unchecked(target_block_index);
 
         // select the bit in the block
-        let mut bit_index = 0;
-        while local_rank < remaining_rank {
-            if block & 0b1 == 0b1 {
-                local_rank += 1;
-            }
-            block >>= 1;
-            bit_index += 1;
-        }
+        let bit_index = select_in_word(block, remaining_rank - local_rank - 1) + 1;
 
-        Some(target_block_index * BitVec::CONTAINER_WIDTH + bit_index)
+        Some(target_block_index * W::BITS + bit_index)
     }
 
     #[inline]
-    unsafe fn select0(&self, data: &BitVec, rank0: usize) -> Option<usize> {
+    unsafe fn select0(&self, data: &BitVec<W>, rank0: usize) -> Option<usize> {
         // // By definition, select0(0) = 0
         if rank0 == 0 {
             return Some(0);
@@ -236,13 +320,12 @@ impl SelectSupport<BitVec> for DenseSamplingRank {
             return None;
         }
 
-        let mut left = 0;
-        let mut right = self.superblocks.len() - 1;
+        let (mut left, mut right) = self.superblock_hints(&self.select0_samples, rank0);
 
         while right - left > 1 {
             let mid = (left + right) / 2;
             let bits_before_mid = mid * self.superblock_size;
-            let mid_rank0 = bits_before_mid - *self.superblocks.get_unchecked(mid);
+            let mid_rank0 = bits_before_mid - self.superblocks.get_unchecked(mid);
             if mid_rank0 < rank0 {
                 left = mid;
             } else {
@@ -250,14 +333,21 @@ impl SelectSupport<BitVec> for DenseSamplingRank {
             }
         }
 
+        // The total rank is pushed as the last superblock only when there are unsampled 1s, so
+        // the last superblock may be reached by the 0s we are looking for without being
+        // covered by the binary search (e.g. when the last, not fully sampled, superblock is all 0s).
+        let bits_before_right = right * self.superblock_size;
+        let right_rank0 = bits_before_right - self.superblocks.get_unchecked(right);
+        if right_rank0 < rank0 {
+            left = right;
+        }
+
         // search for the block that contains the rank.
-        // We know for sure that in left position is the superblock value that is the greatest
-        // of the ranks <= rank, as the last position is always greater or equal than the rank,
-        // as the total rank is the last superblock value.
-        // search for the block that contains the rank
+        // At this point, the left position is the superblock value that is the greatest
+        // of the ranks0 < rank0.
         let bits_before_left = left * self.superblock_size;
 
-        let superblock_rank0 = bits_before_left - *self.superblocks.get_unchecked(left);
+        let superblock_rank0 = bits_before_left - self.superblocks.get_unchecked(left);
         let remaining_rank0 = rank0 - superblock_rank0;
         let raw_data = data.raw_data();
 
@@ -267,7 +357,7 @@ impl SelectSupport<BitVec> for DenseSamplingRank {
         let mut right_block_index = min(left_block_index + self.k - 1, raw_data.len() - 1);
         while right_block_index - left_block_index > 1 {
             let mid = (left_block_index + right_block_index) / 2;
-            let bits_before_mid = (mid - first_block_index) * BitVec::CONTAINER_WIDTH;
+            let bits_before_mid = (mid - first_block_index) * W::BITS;
             let mid_rank0 = bits_before_mid - self.blocks.get_unchecked(mid);
             if mid_rank0 < remaining_rank0 {
                 left_block_index = mid;
@@ -281,7 +371,7 @@ impl SelectSupport<BitVec> for DenseSamplingRank {
         // the right block, otherwise, we select the left block. This happens for example
         // when searching by number 5 in the following blockvector: `1 2 3 4`, left would
         // point to 3 and right to 4, but our target_block_index must be the one targeted by `4`
-        let bits_before_right = (right_block_index - first_block_index) * BitVec::CONTAINER_WIDTH;
+        let bits_before_right = (right_block_index - first_block_index) * W::BITS;
         let right_rank0 = bits_before_right - self.blocks.get_unchecked(right_block_index);
         // check whether the right index contains the greatest of the lessers (we do not have the binary
         // search bounds trick here)
@@ -291,34 +381,59 @@ impl SelectSupport<BitVec> for DenseSamplingRank {
             left_block_index
         };
 
-        let bits_before_target = (target_block_index - first_block_index) * BitVec::CONTAINER_WIDTH;
-        let mut local_rank0 = bits_before_target - self.blocks.get_unchecked(target_block_index);
+        let bits_before_target = (target_block_index - first_block_index) * W::BITS;
+        let local_rank0 = bits_before_target - self.blocks.get_unchecked(target_block_index);
 
         // at this point, we are exactly that the block that contains the rank is `target_block_index`
-        let mut block = *raw_data.get_unchecked(target_block_index);
+        let block = *raw_data.get_unchecked(target_block_index);
 
         // select the bit in the block
-        let mut bit_index = 0;
-        while local_rank0 < remaining_rank0 {
-            if block & 0b1 == 0b0 {
-                local_rank0 += 1;
-            }
-            block >>= 1;
-            bit_index += 1;
-        }
+        let bit_index = select_in_word(!block, remaining_rank0 - local_rank0 - 1) + 1;
 
-        Some(target_block_index * BitVec::CONTAINER_WIDTH + bit_index)
+        Some(target_block_index * W::BITS + bit_index)
     }
 }
 
 pub struct DenseSamplingRankSpec {
     pub k: usize,
+    pub s: Option<usize>,
+    pub counter_width: CounterWidth,
 }
 
 impl DenseSamplingRankSpec {
     #[inline]
     pub const fn new(k: usize) -> Self {
-        Self { k }
+        Self {
+            k,
+            s: None,
+            counter_width: CounterWidth::Auto,
+        }
+    }
+
+    /// Stores the superblock ranks with the given width instead of choosing it from the
+    /// length of the bit vector.
+    #[inline]
+    pub const fn with_counter_width(self, counter_width: CounterWidth) -> Self {
+        Self {
+            counter_width,
+            ..self
+        }
+    }
+
+    /// Also samples the superblock of every `s`-th 1 and every `s`-th 0, so select and
+    /// select0 only binary-search the superblocks between two samples.
+    #[inline]
+    pub const fn with_select_samples(self, s: usize) -> Self {
+        Self { s: Some(s), ..self }
+    }
+
+    #[inline]
+    fn build_rank<W: Word>(&self, data: &BitVec<W>) -> DenseSamplingRank {
+        let mut rs = DenseSamplingRank::new_with_counter_width(data, self.k, self.counter_width);
+        if let Some(s) = self.s {
+            rs.sample_select(data, s);
+        }
+        rs
     }
 }
 
@@ -329,18 +444,20 @@ impl DenseSamplingRank {
     }
 }
 
-impl Build<BitVec, RankStructure<BitVec, DenseSamplingRank>> for DenseSamplingRankSpec {
+impl<W: Word> Build<BitVec<W>, RankStructure<BitVec<W>, DenseSamplingRank>>
+    for DenseSamplingRankSpec
+{
     #[inline]
-    fn build(&self, data: BitVec) -> RankStructure<BitVec, DenseSamplingRank> {
-        let sparse_sampling = DenseSamplingRank::new(&data, self.k);
-        unsafe { RankStructure::new(data, sparse_sampling) }
+    fn build(&self, data: BitVec<W>) -> RankStructure<BitVec<W>, DenseSamplingRank> {
+        let dense_sampling = self.build_rank(&data);
+        unsafe { RankStructure::new(data, dense_sampling) }
     }
 }
 
-impl Build<&BitVec, DenseSamplingRank> for DenseSamplingRankSpec {
+impl<W: Word> Build<&BitVec<W>, DenseSamplingRank> for DenseSamplingRankSpec {
     #[inline]
-    fn build(&self, data: &BitVec) -> DenseSamplingRank {
-        DenseSamplingRank::new(data, self.k)
+    fn build(&self, data: &BitVec<W>) -> DenseSamplingRank {
+        self.build_rank(data)
     }
 }
 
@@ -1,4 +1,9 @@
-use crate::bit_vectors::{rank_select::tests_utils::test_rank_select_access_for, BitVec};
+use crate::bit_vectors::{
+    rank_select::tests_utils::{
+        assert_rank, assert_select, assert_select0, test_for_words, test_rank_select_access_for,
+    },
+    BitVec,
+};
 
 use super::*;
 const WORD_SIZE: usize = std::mem::size_of::<usize>() * 8;
@@ -16,7 +21,7 @@ fn new() {
     assert_eq!(rs.superblock_size, 4 * WORD_SIZE);
     assert_eq!(rs.k, 4);
 
-    assert_eq!(rs.superblocks(), &[0, 4, 9, 11]);
+    assert_eq!(rs.superblocks().iter().collect::<Vec<_>>(), [0, 4, 9, 11]);
     let blocks = rs.blocks().iter().collect::<Vec<_>>();
     assert_eq!(&blocks, &[0, 1, 2, 2, 0, 0, 2, 2, 0, 1, 2]);
 }
@@ -35,11 +40,32 @@ fn new_when_fully_sampled() {
     assert_eq!(rs.superblock_size, 4 * WORD_SIZE);
     assert_eq!(rs.k, 4);
 
-    assert_eq!(rs.superblocks(), &[0, 4, 9]);
+    assert_eq!(rs.superblocks().iter().collect::<Vec<_>>(), [0, 4, 9]);
     let blocks = rs.blocks().iter().collect::<Vec<_>>();
     assert_eq!(&blocks, &[0, 1, 2, 2, 0, 0, 2, 2, 0]);
 }
 
+#[test]
+fn select0_when_zeros_cross_into_the_last_superblock() {
+    use crate::bit_vectors::rank_select::Select;
+
+    // 2 superblocks of 1s, followed by 0s that run past the last sampled superblock into
+    // the one that is not fully sampled. There are no unsampled 1s, so the total rank is
+    // not pushed as a last superblock.
+    let ones = 4 * WORD_SIZE;
+    let len = ones + 2 * WORD_SIZE + WORD_SIZE / 2;
+    let mut bv = BitVec::from_value(true, ones);
+    for _ in ones..len {
+        bv.push(false);
+    }
+    let rs = DenseSamplingRank::spec(2).build(bv);
+
+    for rank0 in 1..=len - ones {
+        assert_eq!(rs.select0(rank0), Some(ones + rank0), "select0({rank0})");
+    }
+    assert_eq!(rs.select0(len - ones + 1), None);
+}
+
 macro_rules! test_constant_time_for_k{
     ($( $k: expr ),*) => {
         $(
@@ -64,7 +90,7 @@ macro_rules! test_constant_time_for_k{
                     let mut acc = 0;
                     for superblock in rs.superblocks().iter().skip(1) {
                         acc += WORD_SIZE * $k;
-                        assert_eq!(*superblock, acc);
+                        assert_eq!(superblock, acc);
                     }
                     assert_eq!(acc, BV_SIZE);
                 }
@@ -85,10 +111,10 @@ macro_rules! test_constant_time_for_k{
                     let mut acc = 0;
                     for superblock in rs.superblocks().iter().skip(1).take(DESIRED_NUM_SUPERBLOCKS) {
                         acc += WORD_SIZE * $k;
-                        assert_eq!(*superblock, acc);
+                        assert_eq!(superblock, acc);
                     }
                     // The last superblock is not fully sampled, as BV_SIZE is not multiple of the superblock size
-                    assert_eq!(rs.superblocks().last().copied(), Some(BV_SIZE));
+                    assert_eq!(rs.superblocks().iter().last(), Some(BV_SIZE));
                     // Do not count the non-multiple block since it has no superblock associated
                     assert_eq!(acc, BV_SIZE - WORD_SIZE / 2);
                 }
@@ -96,7 +122,10 @@ macro_rules! test_constant_time_for_k{
                 fn heap_size_in_bits() {
                     let bv = BitVec::from_value(true, WORD_SIZE * 420 * $k);
                     let rs = DenseSamplingRank::new(&bv, $k);
-                    let superblocks_overhead = (bv.len()/WORD_SIZE/$k + 1 )* WORD_SIZE;
+                    // The superblocks are stored in a CompactIntVec, which takes whole words
+                    let counter_bits = bv.len().bits_required() as usize;
+                    let superblocks_overhead =
+                        ((bv.len() / WORD_SIZE / $k + 1) * counter_bits).div_ceil(WORD_SIZE) * WORD_SIZE;
                     let mut blocks_overhead = (WORD_SIZE * ($k-1)).bits_required() as usize * (bv.len() / WORD_SIZE + 1);
 
                     // CompactIntVec overhead may not be multiple of a word
@@ -118,3 +147,155 @@ macro_rules! test_constant_time_for_k{
 }
 
 test_constant_time_for_k!(1, 2, 4, 5, 8, 16, 20, 32);
+
+test_for_words!(rank_and_select, |bv| {
+    let rs = DenseSamplingRank::spec(3).build(bv.clone());
+    assert_rank(&bv, &rs);
+    assert_select(&bv, &rs);
+    assert_select0(&bv, &rs);
+});
+
+#[test]
+fn new_with_select_samples() {
+    const TEST_DATA: [usize; 10] = [
+        0b1000, 0b0010, 0b0000, 0b0110, 0b0000, 0b1010, 0b0000, 0b1011, 0b0100, 0b0001,
+    ];
+    let bv = BitVec::from(TEST_DATA);
+
+    let rs = DenseSamplingRank::new_with_select_samples(&bv, 2, 3);
+
+    assert_eq!(rs.s(), Some(3));
+    // The 1s with rank 1, 4, 7 and 10 are at the words 0, 3, 7 and 8
+    let select_samples = rs.select_samples().iter().collect::<Vec<_>>();
+    assert_eq!(&select_samples, &[0, 1, 3, 4]);
+    let select0_samples = rs.select0_samples().iter().collect::<Vec<_>>();
+    let expected = bv
+        .iter_zeros()
+        .step_by(3)
+        .map(|position| position / (2 * WORD_SIZE))
+        .collect::<Vec<_>>();
+    assert_eq!(select0_samples, expected);
+
+    assert_eq!(
+        rs.heap_size_in_bits(),
+        DenseSamplingRank::new(&bv, 2).heap_size_in_bits()
+            + rs.select_samples().heap_size_in_bits()
+            + rs.select0_samples().heap_size_in_bits()
+    );
+}
+
+#[test]
+fn spec_without_select_samples() {
+    let bv: BitVec = BitVec::from_value(true, 1000);
+    let rs: DenseSamplingRank = DenseSamplingRank::spec(4).build(&bv);
+
+    assert_eq!(rs.s(), None);
+    assert!(rs.select_samples().is_empty());
+    assert!(rs.select0_samples().is_empty());
+}
+
+macro_rules! test_select_samples_for {
+    ($(($k: expr, $s: expr)),*) => {
+        $(
+            paste::paste! {
+                #[test]
+                fn [<select_with_samples_when_k_is_ $k _and_s_is_ $s>]() {
+                    use crate::bit_vectors::rank_select::{Rank, Select};
+
+                    // Runs of 1s and 0s, so some samples are many superblocks apart
+                    let bv = (0..20_000)
+                        .map(|i| (i / 1000) % 3 == 0 || (i % 7 == 0 && rand::random::<bool>()))
+                        .collect::<BitVec>();
+                    let rs = DenseSamplingRank::spec($k)
+                        .with_select_samples($s)
+                        .build(bv.clone());
+
+                    for (rank, position) in bv.iter_ones().enumerate() {
+                        assert_eq!(rs.select(rank + 1), Some(position + 1));
+                    }
+                    for (rank0, position) in bv.iter_zeros().enumerate() {
+                        assert_eq!(rs.select0(rank0 + 1), Some(position + 1));
+                    }
+                    assert_eq!(rs.select(0), Some(0));
+                    assert_eq!(rs.select0(0), Some(0));
+                    assert_eq!(rs.select(bv.count_ones(0..bv.len()) + 1), None);
+                    assert_eq!(rs.select0(bv.count_zeros(0..bv.len()) + 1), None);
+                    assert_eq!(rs.rank(bv.len()), Some(bv.count_ones(0..bv.len())));
+                }
+            }
+        )*
+    };
+}
+
+test_select_samples_for!((1, 1), (1, 64), (4, 7), (4, 512), (8, 100_000), (20, 32));
+
+#[test]
+fn select_with_samples_when_last_block_is_not_full() {
+    use crate::bit_vectors::rank_select::Select;
+
+    for len in [1, 63, 65, 300, 1000, 1030] {
+        for value in [true, false] {
+            let bv: BitVec = BitVec::from_value(value, len);
+            let rs = DenseSamplingRank::spec(2)
+                .with_select_samples(3)
+                .build(bv.clone());
+            for rank in 0..=len {
+                let expected = if value {
+                    Some(rank)
+                } else {
+                    (rank == 0).then_some(0)
+                };
+                assert_eq!(rs.select(rank), expected);
+                let expected0 = if value {
+                    (rank == 0).then_some(0)
+                } else {
+                    Some(rank)
+                };
+                assert_eq!(rs.select0(rank), expected0);
+            }
+        }
+    }
+}
+
+#[test]
+fn counter_widths() {
+    use crate::bit_vectors::rank_select::{CounterWidth, Rank, Select};
+
+    let bv = (0..10_000)
+        .map(|_| rand::random::<bool>())
+        .collect::<BitVec>();
+    let u64_rs = DenseSamplingRank::spec(2)
+        .with_counter_width(CounterWidth::U64)
+        .build(bv.clone());
+    assert_eq!(
+        u64_rs.rank_support().superblocks().width(),
+        CounterWidth::U64
+    );
+
+    for (width, expected_width, counter_bits) in [
+        (CounterWidth::Auto, CounterWidth::Compact, 14),
+        (CounterWidth::U32, CounterWidth::U32, 32),
+        (CounterWidth::Compact, CounterWidth::Compact, 14),
+    ] {
+        let rs = DenseSamplingRank::spec(2)
+            .with_counter_width(width)
+            .build(bv.clone());
+        let superblocks = rs.rank_support().superblocks();
+        assert_eq!(superblocks.width(), expected_width);
+        assert!(superblocks
+            .iter()
+            .eq(u64_rs.rank_support().superblocks().iter()));
+        assert_eq!(
+            u64_rs.rank_support().heap_size_in_bits() - rs.rank_support().heap_size_in_bits(),
+            superblocks.len() * 64 - superblocks.heap_size_in_bits()
+        );
+        assert!(superblocks.heap_size_in_bits() >= superblocks.len() * counter_bits);
+        assert!(superblocks.heap_size_in_bits() < superblocks.len() * counter_bits + 64);
+
+        for i in 0..=bv.len() {
+            assert_eq!(rs.rank(i), u64_rs.rank(i));
+            assert_eq!(rs.select(i), u64_rs.select(i));
+            assert_eq!(rs.select0(i), u64_rs.select0(i));
+        }
+    }
+}
@@ -15,12 +15,14 @@ pub trait Rank {
     }
 }
 
+/// Select returns the position after the selected bit, that is, the smallest index such
+/// that rank(index) == rank, which is why the implementations add 1 to the index of the
+/// bit they find. By definition, select(0) == 0 and select0(0) == 0.
 pub trait Select {
-    /// Return the index of the i-th 1 in the bit vector.
-    /// select(0) == 0
+    /// Returns the position after the rank-th 1 in the bit vector.
     fn select(&self, rank: usize) -> Option<usize>;
 
-    /// Return the index of the i-th 0 in the bit vector.
+    /// Returns the position after the rank0-th 0 in the bit vector.
     fn select0(&self, rank0: usize) -> Option<usize>;
 }
 
@@ -39,19 +41,49 @@ pub trait RankSupport<T> {
     }
 }
 
+/// Trait that defines structures that support select along with the bit vector, with the
+/// same convention as [Select]: the position after the selected bit is returned.
 pub trait SelectSupport<T> {
-    /// Return the index of the i-th 1 in the bit vector.
-    /// select(0) == 0
+    /// Returns the position after the rank-th 1 in the bit vector.
     /// # Safety
     /// The data used must be the same data that the structure was built with.
     unsafe fn select(&self, data: &T, rank: usize) -> Option<usize>;
 
-    /// Return the index of the i-th 0 in the bit vector.
+    /// Returns the position after the rank0-th 0 in the bit vector.
     /// # Safety
     /// The data used must be the same data that the structure was built with.
     unsafe fn select0(&self, data: &T, rank0: usize) -> Option<usize>;
 }
 
+/// Trait that defines structures that themselves support rank and select over the
+/// occurrences of a bit pattern.
+pub trait PatternRank {
+    /// Returns the number of occurrences of the pattern that are fully in the range
+    /// [0, index). If index is out of bounds, returns None.
+    /// By definition, rank_pattern(0) == 0
+    fn rank_pattern(&self, index: usize) -> Option<usize>;
+
+    /// Returns the position after the last bit of the rank-th occurrence of the pattern,
+    /// that is, the smallest index such that rank_pattern(index) == rank.
+    /// select_pattern(0) == 0
+    fn select_pattern(&self, rank: usize) -> Option<usize>;
+}
+
+/// Trait that defines structures that support rank and select over the occurrences of
+/// a bit pattern along with the bit vector.
+pub trait PatternRankSupport<T> {
+    /// Returns the number of occurrences of the pattern that are fully in the range
+    /// [0, index).
+    /// # Safety
+    /// The data used must be the same data that the structure was built with.
+    unsafe fn rank_pattern(&self, data: &T, index: usize) -> Option<usize>;
+
+    /// Returns the position after the last bit of the rank-th occurrence of the pattern.
+    /// # Safety
+    /// The data used must be the same data that the structure was built with.
+    unsafe fn select_pattern(&self, data: &T, rank: usize) -> Option<usize>;
+}
+
 #[derive(Debug)]
 pub struct RankStructure<T, R>
 where
@@ -283,14 +315,78 @@ where
     }
 }
 
+#[derive(Debug)]
+pub struct PatternRankStructure<T, P>
+where
+    P: PatternRankSupport<T>,
+{
+    data: T,
+    pattern_rank_support: P,
+}
+
+impl<T, P> PatternRankStructure<T, P>
+where
+    P: PatternRankSupport<T>,
+{
+    /// # Safety
+    /// The data used must be the same data that the structure was built with.
+    pub unsafe fn new(data: T, pattern_rank_support: P) -> Self {
+        Self {
+            data,
+            pattern_rank_support,
+        }
+    }
+
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    pub fn pattern_rank_support(&self) -> &P {
+        &self.pattern_rank_support
+    }
+}
+
+impl<T, P> PatternRank for PatternRankStructure<T, P>
+where
+    P: PatternRankSupport<T>,
+{
+    fn rank_pattern(&self, index: usize) -> Option<usize> {
+        unsafe { self.pattern_rank_support.rank_pattern(&self.data, index) }
+    }
+
+    fn select_pattern(&self, rank: usize) -> Option<usize> {
+        unsafe { self.pattern_rank_support.select_pattern(&self.data, rank) }
+    }
+}
+
+impl<T, P> Access for PatternRankStructure<T, P>
+where
+    T: Access,
+    P: PatternRankSupport<T>,
+{
+    fn access(&self, index: usize) -> Option<bool> {
+        self.data.access(index)
+    }
+}
+
+impl<T, P> HeapSize for PatternRankStructure<T, P>
+where
+    T: HeapSize,
+    P: HeapSize + PatternRankSupport<T>,
+{
+    fn heap_size_in_bits(&self) -> usize {
+        self.data.heap_size_in_bits() + self.pattern_rank_support.heap_size_in_bits()
+    }
+}
+
 // Build RankSelectStructure in a generic way, using a RankStructure and a SelectStructure
 // This allow us to use different structures to support these operations, in a centralized
 // and error-free way.
 
-impl<T, R, S, B> Build<RankStructure<T, R>, RankSelectStructure<T, R, S>> for B
+impl<T, R, S, B> crate::Build<RankStructure<T, R>, RankSelectStructure<T, R, S>> for B
 where
     R: RankSupport<T>,
-    B: Build<T, SelectStructure<T, S>>,
+    B: crate::Build<T, SelectStructure<T, S>>,
     S: SelectSupport<T>,
 {
     fn build(&self, data: RankStructure<T, R>) -> RankSelectStructure<T, R, S> {
@@ -304,10 +400,10 @@ where
     }
 }
 
-impl<T, R, S, B> Build<SelectStructure<T, S>, RankSelectStructure<T, R, S>> for B
+impl<T, R, S, B> crate::Build<SelectStructure<T, S>, RankSelectStructure<T, R, S>> for B
 where
     R: RankSupport<T>,
-    B: Build<T, RankStructure<T, R>>,
+    B: crate::Build<T, RankStructure<T, R>>,
     S: SelectSupport<T>,
 {
     fn build(&self, data: SelectStructure<T, S>) -> RankSelectStructure<T, R, S> {
@@ -321,9 +417,14 @@ where
     }
 }
 
-pub trait Build<T, O> {
-    fn build(&self, data: T) -> O;
-}
+/// Former name of [crate::Build], kept so that existing bounds on it still compile.
+/// Every implementor of [crate::Build] implements it, and `build` resolves to the
+/// method of [crate::Build].
+#[deprecated(note = "the specs implement crate::Build, use it instead")]
+pub trait Build<T, O>: crate::Build<T, O> {}
+
+#[allow(deprecated)]
+impl<T, O, B> Build<T, O> for B where B: crate::Build<T, O> {}
 
 // pub trait RankSelect: Rank + Select {}
 
@@ -335,8 +436,23 @@ pub use sparse_sampling_rank::SparseSamplingRank;
 pub mod dense_sampling_rank;
 pub use dense_sampling_rank::DenseSamplingRank;
 
-// TODO: not implemented yet
-// pub mod sparse_sampling_select;
+pub mod darray;
+pub use darray::DArray;
+
+pub mod poppy;
+pub use poppy::{Poppy, PoppyRankSelect};
+
+pub mod rank9;
+pub use rank9::Rank9;
+
+pub mod sparse_sampling_select;
+pub use sparse_sampling_select::SparseSamplingSelect;
+
+pub mod dense_sampling_pattern_rank;
+pub use dense_sampling_pattern_rank::{BitPattern, BitPatternError, DenseSamplingPatternRank};
+
+pub mod superblock_counters;
+pub use superblock_counters::{CounterWidth, SuperblockCounters};
 
 use crate::profiling::HeapSize;
 
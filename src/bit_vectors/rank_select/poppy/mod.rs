@@ -0,0 +1,335 @@
+//! Rank/Select data structure with a three-level directory, as described by Zhou, Andersen
+//! and Kaminsky in "Space-Efficient, High-Performance Rank & Select Structures on
+//! Uncompressed Bit Sequences" (cs-poppy).
+//!
+//! The bit vector is split into basic blocks of 512 bits, the size of a cache line, which
+//! are grouped in lower blocks of 2048 bits, which are grouped in upper blocks of 2^32 bits.
+//! Upper blocks store their absolute rank in a 64-bit word. Every lower block stores in a
+//! single 64-bit word its rank relative to its upper block, in 32 bits, and the number of
+//! 1s of its first three basic blocks, in 10 bits each. This is a 3.125% overhead, so a
+//! rank query reads one cache line of counters and at most one cache line of data.
+//!
+//! Select samples the lower block of every 8192-th 1 and 0, so the binary search over
+//! the lower blocks only covers the range between two samples. The samples take only the
+//! bits needed for a lower block index, so a single Poppy answers both rank and select
+//! with about 3.3% of overhead.
+
+use super::{RankStructure, RankSupport, SelectStructure, SelectSupport};
+use crate::Build;
+use crate::{
+    bit_vectors::BitVec,
+    int_vectors::CompactIntVec,
+    profiling::HeapSize,
+    util::{select_in_word, BitsRequired, Word},
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::min;
+
+const BASIC_BLOCK_SIZE: usize = 512;
+const LOWER_BLOCK_SIZE: usize = 4 * BASIC_BLOCK_SIZE;
+const UPPER_BLOCK_SIZE_BITS: usize = 32;
+const BASIC_BLOCK_COUNT_WIDTH: usize = 10;
+const SELECT_SAMPLE_RATE: usize = 8192;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Poppy {
+    upper_blocks: Vec<u64>,
+    lower_blocks: Vec<u64>,
+    select_samples: CompactIntVec,
+    select0_samples: CompactIntVec,
+    total_rank: usize,
+}
+
+impl Poppy {
+    #[inline]
+    pub fn new<W: Word>(data: &BitVec<W>) -> Self {
+        let n = data.len();
+        // As in the other structures, there is an entry for index == n
+        let num_lower_blocks = n / LOWER_BLOCK_SIZE + 1;
+        let mut upper_blocks = Vec::with_capacity(Self::upper_block(n) + 1);
+        let mut lower_blocks = Vec::with_capacity(num_lower_blocks);
+        let sample_width = (num_lower_blocks - 1).bits_required() as usize;
+        let mut select_samples = CompactIntVec::new(sample_width);
+        let mut select0_samples = CompactIntVec::new(sample_width);
+
+        let mut rank = 0;
+        let mut upper_block_rank = 0;
+        for i in 0..num_lower_blocks {
+            let lower_block_start = i * LOWER_BLOCK_SIZE;
+            if upper_blocks.len() == Self::upper_block(lower_block_start) {
+                upper_blocks.push(rank as u64);
+                upper_block_rank = rank;
+            }
+
+            let mut entry = (rank - upper_block_rank) as u64;
+            let mut lower_block_rank = 0;
+            for j in 0..4 {
+                let start = min(lower_block_start + j * BASIC_BLOCK_SIZE, n);
+                let end = min(start + BASIC_BLOCK_SIZE, n);
+                let basic_block_rank = data.count_ones(start..end);
+                if j < 3 {
+                    entry |= (basic_block_rank as u64)
+                        << (UPPER_BLOCK_SIZE_BITS + j * BASIC_BLOCK_COUNT_WIDTH);
+                }
+                lower_block_rank += basic_block_rank;
+            }
+            lower_blocks.push(entry);
+
+            // Sample the lower blocks that contain the 1s and 0s with rank 1, 8193, 16385...
+            let lower_block_end = min(lower_block_start + LOWER_BLOCK_SIZE, n);
+            let rank0 = min(lower_block_start, n) - rank;
+            let lower_block_rank0 = lower_block_end - min(lower_block_start, n) - lower_block_rank;
+            for (samples, last_rank) in [
+                (&mut select_samples, rank + lower_block_rank),
+                (&mut select0_samples, rank0 + lower_block_rank0),
+            ] {
+                while samples.len() * SELECT_SAMPLE_RATE < last_rank {
+                    samples.push(i);
+                }
+            }
+
+            rank += lower_block_rank;
+        }
+
+        Self {
+            upper_blocks,
+            lower_blocks,
+            select_samples,
+            select0_samples,
+            total_rank: rank,
+        }
+    }
+
+    /// Returns the index of the upper block that contains `position`.
+    #[inline]
+    fn upper_block(position: usize) -> usize {
+        // Upper blocks are as big as the address space of 32-bit targets
+        ((position as u64) >> UPPER_BLOCK_SIZE_BITS) as usize
+    }
+
+    /// Returns the number of 1s before the lower block `index`.
+    ///
+    /// # Safety
+    /// `index` must be the index of a lower block.
+    #[inline]
+    unsafe fn lower_block_rank_unchecked(&self, index: usize) -> usize {
+        let upper_block = Self::upper_block(index * LOWER_BLOCK_SIZE);
+        let entry = *self.lower_blocks.get_unchecked(index);
+        (*self.upper_blocks.get_unchecked(upper_block) + (entry & u32::MAX as u64)) as usize
+    }
+
+    /// Returns the number of 1s in the basic block `j` of the lower block with `entry`.
+    #[inline]
+    fn basic_block_rank(entry: u64, j: usize) -> usize {
+        ((entry >> (UPPER_BLOCK_SIZE_BITS + j * BASIC_BLOCK_COUNT_WIDTH))
+            & ((1 << BASIC_BLOCK_COUNT_WIDTH) - 1)) as usize
+    }
+
+    /// Returns the position after the bit with rank `rank` of the given value, or rank0 if
+    /// `VALUE` is false, using the samples to bound the binary search over the lower blocks.
+    ///
+    /// # Safety
+    /// The data must be the one used to build the structure, and there must be a bit
+    /// with the given rank.
+    #[inline]
+    unsafe fn select_unchecked<W: Word, const VALUE: bool>(
+        &self,
+        data: &BitVec<W>,
+        rank: usize,
+    ) -> usize {
+        let samples = if VALUE {
+            &self.select_samples
+        } else {
+            &self.select0_samples
+        };
+        let lower_block_rank = |index: usize| {
+            let ones = self.lower_block_rank_unchecked(index);
+            if VALUE {
+                ones
+            } else {
+                index * LOWER_BLOCK_SIZE - ones
+            }
+        };
+
+        // Find the last lower block with less than `rank` bits before it
+        let sample = (rank - 1) / SELECT_SAMPLE_RATE;
+        let mut left = samples.get_unchecked(sample);
+        let mut right = samples
+            .get(sample + 1)
+            .unwrap_or(self.lower_blocks.len() - 1);
+        while left < right {
+            let mid = (left + right).div_ceil(2);
+            if lower_block_rank(mid) < rank {
+                left = mid;
+            } else {
+                right = mid - 1;
+            }
+        }
+
+        let mut remaining = rank - lower_block_rank(left);
+        let entry = *self.lower_blocks.get_unchecked(left);
+        let mut basic_block = 0;
+        while basic_block < 3 {
+            let ones = Self::basic_block_rank(entry, basic_block);
+            let count = if VALUE { ones } else { BASIC_BLOCK_SIZE - ones };
+            if remaining <= count {
+                break;
+            }
+            remaining -= count;
+            basic_block += 1;
+        }
+
+        // Scan the words of the basic block. Basic blocks are always made of whole words.
+        let raw_data = data.raw_data();
+        let mut word_index = (left * LOWER_BLOCK_SIZE + basic_block * BASIC_BLOCK_SIZE) / W::BITS;
+        loop {
+            let word = *raw_data.get_unchecked(word_index);
+            let word = if VALUE { word } else { !word };
+            let count = word.count_ones() as usize;
+            if remaining <= count {
+                return word_index * W::BITS + select_in_word(word, remaining - 1) + 1;
+            }
+            remaining -= count;
+            word_index += 1;
+        }
+    }
+}
+
+impl HeapSize for Poppy {
+    #[inline]
+    fn heap_size_in_bits(&self) -> usize {
+        self.upper_blocks.heap_size_in_bits()
+            + self.lower_blocks.heap_size_in_bits()
+            + self.select_samples.heap_size_in_bits()
+            + self.select0_samples.heap_size_in_bits()
+    }
+}
+
+impl<W: Word> RankSupport<BitVec<W>> for Poppy {
+    #[inline]
+    unsafe fn rank(&self, data: &BitVec<W>, index: usize) -> Option<usize> {
+        if index > data.len() {
+            return None;
+        }
+
+        let lower_block = index / LOWER_BLOCK_SIZE;
+        let entry = *self.lower_blocks.get_unchecked(lower_block);
+        let mut rank = self.lower_block_rank_unchecked(lower_block);
+        let basic_block = (index / BASIC_BLOCK_SIZE) % 4;
+        for j in 0..basic_block {
+            rank += Self::basic_block_rank(entry, j);
+        }
+
+        let basic_block_start = index - index % BASIC_BLOCK_SIZE;
+        Some(rank + data.count_ones(basic_block_start..index))
+    }
+}
+
+impl<W: Word> SelectSupport<BitVec<W>> for Poppy {
+    #[inline]
+    unsafe fn select(&self, data: &BitVec<W>, rank: usize) -> Option<usize> {
+        // By definition, select(0) = 0
+        if rank == 0 {
+            return Some(0);
+        }
+        if rank > self.total_rank {
+            return None;
+        }
+        Some(self.select_unchecked::<W, true>(data, rank))
+    }
+
+    #[inline]
+    unsafe fn select0(&self, data: &BitVec<W>, rank0: usize) -> Option<usize> {
+        // By definition, select0(0) = 0
+        if rank0 == 0 {
+            return Some(0);
+        }
+        if rank0 > data.len() - self.total_rank {
+            return None;
+        }
+        Some(self.select_unchecked::<W, false>(data, rank0))
+    }
+}
+
+/// Bit vector with a single Poppy, which supports both rank and select. Build it
+/// with [Poppy::spec].
+pub type PoppyRankSelect<W = usize> = RankStructure<BitVec<W>, Poppy>;
+
+/// Spec that builds a [PoppyRankSelect], where the same Poppy answers rank and select.
+pub struct PoppySpec;
+
+impl PoppySpec {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for PoppySpec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Spec that builds Poppy as the select support of a SelectStructure, so it can be
+/// combined with another rank structure into a RankSelectStructure. To use Poppy for
+/// both, build a [PoppyRankSelect] with [Poppy::spec] instead, which has half the overhead.
+pub struct PoppySelectSpec;
+
+impl PoppySelectSpec {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for PoppySelectSpec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Poppy {
+    #[inline]
+    pub const fn spec() -> PoppySpec {
+        PoppySpec::new()
+    }
+
+    #[inline]
+    pub const fn select_spec() -> PoppySelectSpec {
+        PoppySelectSpec::new()
+    }
+}
+
+impl<W: Word> Build<BitVec<W>, PoppyRankSelect<W>> for PoppySpec {
+    #[inline]
+    fn build(&self, data: BitVec<W>) -> PoppyRankSelect<W> {
+        let poppy = Poppy::new(&data);
+        unsafe { RankStructure::new(data, poppy) }
+    }
+}
+
+impl<W: Word> Build<&BitVec<W>, Poppy> for PoppySpec {
+    #[inline]
+    fn build(&self, data: &BitVec<W>) -> Poppy {
+        Poppy::new(data)
+    }
+}
+
+impl<W: Word> Build<BitVec<W>, SelectStructure<BitVec<W>, Poppy>> for PoppySelectSpec {
+    #[inline]
+    fn build(&self, data: BitVec<W>) -> SelectStructure<BitVec<W>, Poppy> {
+        let poppy = Poppy::new(&data);
+        unsafe { SelectStructure::new(data, poppy) }
+    }
+}
+
+impl<W: Word> Build<&BitVec<W>, Poppy> for PoppySelectSpec {
+    #[inline]
+    fn build(&self, data: &BitVec<W>) -> Poppy {
+        Poppy::new(data)
+    }
+}
+
+#[cfg(test)]
+mod tests;
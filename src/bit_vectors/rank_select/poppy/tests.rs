@@ -0,0 +1,157 @@
+use super::*;
+use crate::bit_vectors::rank_select::tests_utils::{
+    assert_rank, assert_select, assert_select0, test_for_words, test_rank_select_access_for,
+};
+use crate::bit_vectors::rank_select::{DenseSamplingRank, Rank, RankSelectStructure, Select};
+use crate::test_utils::random_bitvec;
+
+#[test]
+fn new() {
+    // 1 one in every byte, so 64 ones in every basic block
+    let bv = BitVec::from([0b00000100u8; 600]);
+    let rs = Poppy::new(&bv);
+
+    assert_eq!(rs.upper_blocks, [0]);
+    assert_eq!(rs.lower_blocks.len(), 3);
+    let basic_blocks = 64 | (64 << 10) | (64 << 20);
+    assert_eq!(rs.lower_blocks[0], basic_blocks << 32);
+    assert_eq!(rs.lower_blocks[1], 256 | (basic_blocks << 32));
+    // The last lower block has 4800 - 4096 = 704 bits, so the second basic block is partial
+    assert_eq!(rs.lower_blocks[2], 512 | ((64 | (24 << 10)) << 32));
+    assert_eq!(rs.total_rank, 600);
+    assert_eq!(rs.select_samples.iter().collect::<Vec<_>>(), [0]);
+    assert_eq!(rs.select0_samples.iter().collect::<Vec<_>>(), [0]);
+}
+
+#[test]
+fn new_when_empty_bitvec() {
+    let bv: BitVec = BitVec::new();
+    let rs = Poppy::new(&bv);
+
+    assert_eq!(rs.upper_blocks, [0]);
+    assert_eq!(rs.lower_blocks, [0]);
+    assert!(rs.select_samples.is_empty());
+    assert!(rs.select0_samples.is_empty());
+}
+
+#[test]
+fn new_samples_every_8192_bits() {
+    let bv: BitVec = BitVec::from_value(true, 4 * SELECT_SAMPLE_RATE + 1);
+    let rs = Poppy::new(&bv);
+
+    // The 1s with rank 1, 8193, 16385, 24577 and 32769
+    assert_eq!(
+        rs.select_samples.iter().collect::<Vec<_>>(),
+        [0, 4, 8, 12, 16]
+    );
+    assert!(rs.select0_samples.is_empty());
+}
+
+#[test]
+fn upper_block() {
+    assert_eq!(Poppy::upper_block(0), 0);
+    assert_eq!(Poppy::upper_block(u32::MAX as usize), 0);
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(Poppy::upper_block(3 << 32), 3);
+}
+
+#[test]
+fn heap_size_in_bits() {
+    let bv: BitVec = BitVec::from_value(false, 1000 * LOWER_BLOCK_SIZE);
+    let rs = Poppy::new(&bv);
+
+    // 64 bits per lower block, plus an upper block and the samples of the 0s, which
+    // take the 10 bits needed for the index of the last lower block, in whole words
+    let num_samples = 1000 * LOWER_BLOCK_SIZE / SELECT_SAMPLE_RATE;
+    assert_eq!(
+        rs.heap_size_in_bits(),
+        1001 * 64 + 64 + (num_samples * 10).div_ceil(64) * 64
+    );
+}
+
+#[test]
+fn single_poppy_overhead() {
+    let bv = random_bitvec(1 << 22);
+    let rs: PoppyRankSelect = Poppy::spec().build(bv.clone());
+    let overhead = rs.heap_size_in_bytes() as f64 / bv.heap_size_in_bytes() as f64 - 1.0;
+
+    // 3.125% for rank, and the rest for the select samples
+    assert!(overhead > 0.03 && overhead < 0.035, "{overhead}");
+    for i in (0..=bv.len()).step_by(997) {
+        assert_eq!(rs.rank(i), bv.rank(i));
+    }
+    for (i, position) in bv.iter_ones().enumerate().step_by(997) {
+        assert_eq!(rs.select(i + 1), Some(position + 1));
+    }
+    for (i, position) in bv.iter_zeros().enumerate().step_by(997) {
+        assert_eq!(rs.select0(i + 1), Some(position + 1));
+    }
+}
+
+#[test]
+fn operations_with_random_values_over_many_samples() {
+    let bv = random_bitvec(200_000);
+    let rs = Poppy::spec().build(bv.clone());
+
+    assert_rank(&bv, &rs);
+    assert_select(&bv, &rs);
+    assert_select0(&bv, &rs);
+}
+
+#[test]
+fn select_around_sample_boundaries() {
+    // The 8192-th and 8193-th 1s or 0s are the last of a sample and the first of the next
+    for len in [
+        SELECT_SAMPLE_RATE - 1,
+        SELECT_SAMPLE_RATE,
+        SELECT_SAMPLE_RATE + 1,
+        2 * SELECT_SAMPLE_RATE + 1,
+    ] {
+        let alternating = (0..2 * len).map(|i| i % 2 == 1).collect::<BitVec>();
+        for bv in [
+            BitVec::from_value(true, len),
+            BitVec::from_value(false, len),
+            alternating,
+        ] {
+            let rs = Poppy::spec().build(bv.clone());
+            assert_select(&bv, &rs);
+            assert_select0(&bv, &rs);
+        }
+    }
+}
+
+#[test]
+fn build_rank_select_structure() {
+    let bv = random_bitvec(20_000);
+
+    let rs: RankSelectStructure<BitVec, DenseSamplingRank, Poppy> =
+        Poppy::select_spec().build(DenseSamplingRank::spec(4).build(bv.clone()));
+    assert_rank(&bv, &rs);
+    assert_select(&bv, &rs);
+    assert_select0(&bv, &rs);
+}
+
+#[test]
+#[allow(deprecated)]
+fn build_through_the_deprecated_build_trait() {
+    fn build<B>(spec: &B, data: BitVec) -> PoppyRankSelect
+    where
+        B: crate::bit_vectors::rank_select::Build<BitVec, PoppyRankSelect>,
+    {
+        spec.build(data)
+    }
+    let bv = random_bitvec(20_000);
+
+    let rs = build(&Poppy::spec(), bv.clone());
+    assert_rank(&bv, &rs);
+    assert_select(&bv, &rs);
+}
+
+test_rank_select_access_for!(Poppy,);
+
+test_for_words!(rank_and_select, |bv| {
+    let rs = Poppy::spec().build(bv.clone());
+    assert_rank(&bv, &rs);
+    assert_select(&bv, &rs);
+    assert_select0(&bv, &rs);
+});
@@ -0,0 +1,137 @@
+//! Rank data structure with interleaved counters, as described by Vigna in
+//! "Broadword Implementation of Rank/Select Queries".
+//! The bit vector is split into superblocks of 512 bits, which are split into 8 blocks
+//! of 64 bits. Every superblock has a record of two 64-bit words: the rank at the start of
+//! the superblock, and the rank at the start of the blocks 1 to 7 relative to the
+//! superblock, packed in 9 bits each. A rank query then reads a single record.
+
+use super::{RankStructure, RankSupport};
+use crate::Build;
+use crate::{bit_vectors::BitVec, profiling::HeapSize, util::Word};
+use serde::{Deserialize, Serialize};
+use std::cmp::min;
+
+const BLOCK_SIZE: usize = 64;
+const SUPERBLOCK_SIZE: usize = 8 * BLOCK_SIZE;
+const RELATIVE_WIDTH: usize = 9;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Rank9Record {
+    absolute: u64,
+    relative: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Rank9 {
+    records: Vec<Rank9Record>,
+}
+
+impl Rank9 {
+    #[inline]
+    pub fn new<W: Word>(data: &BitVec<W>) -> Self {
+        let n = data.len();
+        // A record is needed for index == n, so there is always one more record than
+        // full superblocks
+        let num_records = n / SUPERBLOCK_SIZE + 1;
+        let mut records = Vec::with_capacity(num_records);
+
+        let mut rank = 0;
+        for i in 0..num_records {
+            let superblock_start = i * SUPERBLOCK_SIZE;
+            let mut relative = 0;
+            let mut relative_rank = 0;
+            for j in 0..7 {
+                let block_start = min(superblock_start + j * BLOCK_SIZE, n);
+                let block_end = min(block_start + BLOCK_SIZE, n);
+                relative_rank += data.count_ones(block_start..block_end) as u64;
+                relative |= relative_rank << (j * RELATIVE_WIDTH);
+            }
+
+            records.push(Rank9Record {
+                absolute: rank as u64,
+                relative,
+            });
+
+            let superblock_end = min(superblock_start + SUPERBLOCK_SIZE, n);
+            rank += data.count_ones(min(superblock_start, n)..superblock_end);
+        }
+
+        Self { records }
+    }
+
+    /// Returns the number of 1s in the superblocks before the superblock `index`.
+    #[inline]
+    pub fn superblock_rank(&self, index: usize) -> Option<usize> {
+        self.records
+            .get(index)
+            .map(|record| record.absolute as usize)
+    }
+}
+
+impl HeapSize for Rank9 {
+    #[inline]
+    fn heap_size_in_bits(&self) -> usize {
+        self.records.heap_size_in_bits()
+    }
+}
+
+impl<W: Word> RankSupport<BitVec<W>> for Rank9 {
+    #[inline]
+    unsafe fn rank(&self, data: &BitVec<W>, index: usize) -> Option<usize> {
+        if index > data.len() {
+            return None;
+        }
+
+        let record = self.records.get_unchecked(index / SUPERBLOCK_SIZE);
+        let block = (index / BLOCK_SIZE) % 8;
+        let relative = if block == 0 {
+            0
+        } else {
+            (record.relative >> ((block - 1) * RELATIVE_WIDTH)) & ((1 << RELATIVE_WIDTH) - 1)
+        };
+
+        let block_start = index - index % BLOCK_SIZE;
+        let rank = record.absolute + relative;
+        Some(rank as usize + data.count_ones(block_start..index))
+    }
+}
+
+pub struct Rank9Spec;
+
+impl Rank9Spec {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for Rank9Spec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rank9 {
+    #[inline]
+    pub const fn spec() -> Rank9Spec {
+        Rank9Spec::new()
+    }
+}
+
+impl<W: Word> Build<BitVec<W>, RankStructure<BitVec<W>, Rank9>> for Rank9Spec {
+    #[inline]
+    fn build(&self, data: BitVec<W>) -> RankStructure<BitVec<W>, Rank9> {
+        let rank9 = Rank9::new(&data);
+        unsafe { RankStructure::new(data, rank9) }
+    }
+}
+
+impl<W: Word> Build<&BitVec<W>, Rank9> for Rank9Spec {
+    #[inline]
+    fn build(&self, data: &BitVec<W>) -> Rank9 {
+        Rank9::new(data)
+    }
+}
+
+#[cfg(test)]
+mod tests;
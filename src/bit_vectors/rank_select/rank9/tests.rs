@@ -0,0 +1,61 @@
+use super::*;
+use crate::bit_vectors::rank_select::tests_utils::{assert_rank, test_for_words, test_rank_for};
+
+#[test]
+fn new() {
+    // 2 superblocks and a half, with 1 one in every byte
+    let bv = BitVec::from([0b00010000u8; 160]);
+    let rs = Rank9::new(&bv);
+
+    assert_eq!(rs.records.len(), 3);
+    assert_eq!(rs.superblock_rank(0), Some(0));
+    assert_eq!(rs.superblock_rank(1), Some(64));
+    assert_eq!(rs.superblock_rank(2), Some(128));
+    assert_eq!(rs.superblock_rank(3), None);
+
+    // Every block has 8 ones
+    let relative = (1..8).fold(0, |acc, j| acc | ((8 * j) << (9 * (j - 1))));
+    assert_eq!(rs.records[0].relative, relative);
+    assert_eq!(rs.records[1].relative, relative);
+    // The last superblock only has 4 blocks, so the relative counts do not grow after them
+    let relative = (1..8).fold(0, |acc, j| {
+        acc | ((8 * std::cmp::min(j, 4)) << (9 * (j - 1)))
+    });
+    assert_eq!(rs.records[2].relative, relative);
+}
+
+#[test]
+fn new_when_len_is_multiple_of_superblock_size() {
+    let bv: BitVec = BitVec::from_value(true, 2 * SUPERBLOCK_SIZE);
+    let rs = Rank9::new(&bv);
+
+    // The last record only holds the total rank
+    assert_eq!(rs.records.len(), 3);
+    assert_eq!(rs.superblock_rank(2), Some(2 * SUPERBLOCK_SIZE));
+    assert_eq!(rs.records[2].relative, 0);
+}
+
+#[test]
+fn new_when_empty_bitvec() {
+    let bv: BitVec = BitVec::new();
+    let rs = Rank9::new(&bv);
+
+    assert_eq!(rs.records.len(), 1);
+    assert_eq!(rs.superblock_rank(0), Some(0));
+}
+
+#[test]
+fn heap_size_in_bits() {
+    let bv: BitVec = BitVec::from_value(true, 100 * SUPERBLOCK_SIZE);
+    let rs = Rank9::new(&bv);
+
+    // 128 bits every 512 bits, which is a 25% overhead
+    assert_eq!(rs.heap_size_in_bits(), 101 * 128);
+}
+
+test_rank_for!(Rank9,);
+
+test_for_words!(rank, |bv| {
+    let rs = Rank9::spec().build(bv.clone());
+    assert_rank(&bv, &rs);
+});
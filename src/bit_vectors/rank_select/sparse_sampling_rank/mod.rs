@@ -3,16 +3,17 @@
 //! machine, so `w` matches with the size of the type `usize`. Each superblock
 //! stores Rank(floor(i/s)) for every index `i`.
 
-use super::{RankStructure, RankSupport, SelectSupport};
+use super::{CounterWidth, RankStructure, RankSupport, SelectSupport, SuperblockCounters};
 use crate::Build;
-use crate::{bit_vectors::BitVec, profiling::HeapSize};
+use crate::{
+    bit_vectors::BitVec,
+    profiling::HeapSize,
+    util::{bitmask, select_in_word, Word},
+};
 
-// TODO: improve superblocks by taking into account that the maximum value
-// it could hold is the number of 1s in the bitvector, using CompactIntVec,
-// as this structure is immutable.
 #[derive(Debug)]
 pub struct SparseSamplingRank {
-    superblocks: Vec<usize>,
+    superblocks: SuperblockCounters,
     superblock_size: usize,
     total_rank: usize,
     k: usize,
@@ -20,12 +21,22 @@ pub struct SparseSamplingRank {
 
 impl SparseSamplingRank {
     #[inline]
-    pub fn new(data: &BitVec, k: usize) -> Self {
+    pub fn new<W: Word>(data: &BitVec<W>, k: usize) -> Self {
+        Self::new_with_counter_width(data, k, CounterWidth::Auto)
+    }
+
+    /// Builds the structure storing the superblock ranks with the given width.
+    pub fn new_with_counter_width<W: Word>(
+        data: &BitVec<W>,
+        k: usize,
+        counter_width: CounterWidth,
+    ) -> Self {
         assert!(k > 0, "k must be greater than 0");
 
-        let superblock_size = k * BitVec::CONTAINER_WIDTH;
+        let superblock_size = k * W::BITS;
         let num_superblocks = data.len() / superblock_size;
-        let mut superblocks = Vec::with_capacity(num_superblocks + 1);
+        let mut superblocks =
+            SuperblockCounters::with_capacity(counter_width, data.len(), num_superblocks + 1);
 
         let mut rank = 0;
         // first superblock is always 0
@@ -66,7 +77,7 @@ impl SparseSamplingRank {
         }
     }
 
-    pub fn superblocks(&self) -> &[usize] {
+    pub fn superblocks(&self) -> &SuperblockCounters {
         &self.superblocks
     }
 
@@ -79,9 +90,9 @@ impl SparseSamplingRank {
     }
 
     #[inline]
-    pub(crate) unsafe fn select_with_hints(
+    pub(crate) unsafe fn select_with_hints<W: Word>(
         &self,
-        data: &BitVec,
+        data: &BitVec<W>,
         rank: usize,
         left: usize,
         right: usize,
@@ -91,7 +102,7 @@ impl SparseSamplingRank {
 
         while right - left > 1 {
             let mid = (left + right) / 2;
-            let mid_rank = *self.superblocks.get_unchecked(mid);
+            let mid_rank = self.superblocks.get_unchecked(mid);
             if mid_rank < rank {
                 left = mid;
             } else {
@@ -103,7 +114,7 @@ impl SparseSamplingRank {
         // We know for sure that in left position is the superblock value that is the greatest
         // of the ranks <= rank, as the last position is always greater or equal than the rank,
         // as the total rank is the last superblock value.
-        let mut local_rank = *self.superblocks.get_unchecked(left);
+        let mut local_rank = self.superblocks.get_unchecked(left);
         let mut block_index = left * self.k;
         let raw_data = data.raw_data();
         let mut block_rank = raw_data.get_unchecked(block_index).count_ones() as usize;
@@ -116,25 +127,18 @@ impl SparseSamplingRank {
         }
 
         // at this point, we are exactly that the block that contains the rank is `block_index`
-        let mut block = *raw_data.get_unchecked(block_index);
+        let block = *raw_data.get_unchecked(block_index);
 
         // select the bit in the block
-        let mut bit_index = 0;
-        while local_rank < rank {
-            if block & 0b1 == 0b1 {
-                local_rank += 1;
-            }
-            block >>= 1;
-            bit_index += 1;
-        }
+        let bit_index = select_in_word(block, rank - local_rank - 1) + 1;
 
-        Some(block_index * BitVec::CONTAINER_WIDTH + bit_index)
+        Some(block_index * W::BITS + bit_index)
     }
 
     #[inline]
-    pub(crate) unsafe fn select0_with_hints(
+    pub(crate) unsafe fn select0_with_hints<W: Word>(
         &self,
-        data: &BitVec,
+        data: &BitVec<W>,
         rank0: usize,
         left: usize,
         right: usize,
@@ -145,7 +149,7 @@ impl SparseSamplingRank {
         while right - left > 1 {
             let mid = (left + right) / 2;
             let bits_before_mid = mid * self.superblock_size;
-            let mid_rank0 = bits_before_mid - *self.superblocks.get_unchecked(mid);
+            let mid_rank0 = bits_before_mid - self.superblocks.get_unchecked(mid);
             if mid_rank0 < rank0 {
                 left = mid;
             } else {
@@ -155,7 +159,7 @@ impl SparseSamplingRank {
 
         // search for the block that contains the rank
         let bits_before_left = left * self.superblock_size;
-        let mut local_rank0 = bits_before_left - *self.superblocks.get_unchecked(left);
+        let mut local_rank0 = bits_before_left - self.superblocks.get_unchecked(left);
         let mut block_index = left * self.k;
         let raw_data = data.raw_data();
         // We dont have to check if the block_index is at last and we are counting bits that are
@@ -171,19 +175,12 @@ impl SparseSamplingRank {
         }
 
         // at this point, we are exactly that the block that contains the rank is `block_index`
-        let mut block = *raw_data.get_unchecked(block_index);
+        let block = *raw_data.get_unchecked(block_index);
 
         // select the bit in the block
-        let mut bit_index = 0;
-        while local_rank0 < rank0 {
-            if block & 0b1 == 0b0 {
-                local_rank0 += 1;
-            }
-            block >>= 1;
-            bit_index += 1;
-        }
+        let bit_index = select_in_word(!block, rank0 - local_rank0 - 1) + 1;
 
-        Some(block_index * BitVec::CONTAINER_WIDTH + bit_index)
+        Some(block_index * W::BITS + bit_index)
     }
 }
 
@@ -194,9 +191,9 @@ impl HeapSize for SparseSamplingRank {
     }
 }
 
-impl RankSupport<BitVec> for SparseSamplingRank {
+impl<W: Word> RankSupport<BitVec<W>> for SparseSamplingRank {
     #[inline]
-    unsafe fn rank(&self, data: &BitVec, index: usize) -> Option<usize> {
+    unsafe fn rank(&self, data: &BitVec<W>, index: usize) -> Option<usize> {
         // By definition, rank(0) = 0
         if index == 0 {
             return Some(0);
@@ -207,9 +204,9 @@ impl RankSupport<BitVec> for SparseSamplingRank {
         }
 
         let is = index / self.superblock_size;
-        let iw = index / BitVec::CONTAINER_WIDTH;
+        let iw = index / W::BITS;
 
-        let mut rank = self.superblocks[is];
+        let mut rank = self.superblocks.get_unchecked(is);
 
         let raw_data = data.raw_data();
         for i in is * self.k..iw {
@@ -218,22 +215,21 @@ impl RankSupport<BitVec> for SparseSamplingRank {
             rank += raw_data.get_unchecked(i).count_ones() as usize;
         }
 
-        let block_offset = index % BitVec::CONTAINER_WIDTH;
-        let last_block = raw_data.get(iw).copied().unwrap_or(0);
-        // getbits!(last_block, block_offset, 0) optimized, we know that block_offset is always <64 so this does not overflow
-        let last_block_target = last_block & ((1 << block_offset) - 1);
+        let block_offset = index % W::BITS;
+        let last_block = raw_data.get(iw).copied().unwrap_or(W::ZERO);
+        let last_block_target = last_block & bitmask!(W, block_offset, 0);
         let last_block_rank = last_block_target.count_ones() as usize;
 
         Some(rank + last_block_rank)
     }
 }
 
-impl SelectSupport<BitVec> for SparseSamplingRank {
+impl<W: Word> SelectSupport<BitVec<W>> for SparseSamplingRank {
     // TODO: extract select in hinted_select private function of sparse sampling rank,
     // so it could be used from here with range (0, len-1) and in
     // sparse select with range i1,i2
     #[inline]
-    unsafe fn select(&self, data: &BitVec, rank: usize) -> Option<usize> {
+    unsafe fn select(&self, data: &BitVec<W>, rank: usize) -> Option<usize> {
         // // By definition, select(0) = 0
         if rank == 0 {
             return Some(0);
@@ -250,7 +246,7 @@ impl SelectSupport<BitVec> for SparseSamplingRank {
     }
 
     #[inline]
-    unsafe fn select0(&self, data: &BitVec, rank0: usize) -> Option<usize> {
+    unsafe fn select0(&self, data: &BitVec<W>, rank0: usize) -> Option<usize> {
         // // By definition, select0(0) = 0
         if rank0 == 0 {
             return Some(0);
@@ -269,12 +265,26 @@ impl SelectSupport<BitVec> for SparseSamplingRank {
 
 pub struct SparseSamplingRankSpec {
     pub k: usize,
+    pub counter_width: CounterWidth,
 }
 
 impl SparseSamplingRankSpec {
     #[inline]
     pub const fn new(k: usize) -> Self {
-        Self { k }
+        Self {
+            k,
+            counter_width: CounterWidth::Auto,
+        }
+    }
+
+    /// Stores the superblock ranks with the given width instead of choosing it from the
+    /// length of the bit vector.
+    #[inline]
+    pub const fn with_counter_width(self, counter_width: CounterWidth) -> Self {
+        Self {
+            counter_width,
+            ..self
+        }
     }
 }
 
@@ -285,17 +295,20 @@ impl SparseSamplingRank {
     }
 }
 
-impl Build<BitVec, RankStructure<BitVec, SparseSamplingRank>> for SparseSamplingRankSpec {
+impl<W: Word> Build<BitVec<W>, RankStructure<BitVec<W>, SparseSamplingRank>>
+    for SparseSamplingRankSpec
+{
     #[inline]
-    fn build(&self, data: BitVec) -> RankStructure<BitVec, SparseSamplingRank> {
-        let sparse_sampling = SparseSamplingRank::new(&data, self.k);
+    fn build(&self, data: BitVec<W>) -> RankStructure<BitVec<W>, SparseSamplingRank> {
+        let sparse_sampling =
+            SparseSamplingRank::new_with_counter_width(&data, self.k, self.counter_width);
         unsafe { RankStructure::new(data, sparse_sampling) }
     }
 }
-impl Build<&BitVec, SparseSamplingRank> for SparseSamplingRankSpec {
+impl<W: Word> Build<&BitVec<W>, SparseSamplingRank> for SparseSamplingRankSpec {
     #[inline]
-    fn build(&self, data: &BitVec) -> SparseSamplingRank {
-        SparseSamplingRank::new(data, self.k)
+    fn build(&self, data: &BitVec<W>) -> SparseSamplingRank {
+        SparseSamplingRank::new_with_counter_width(data, self.k, self.counter_width)
     }
 }
 
@@ -1,5 +1,11 @@
 use super::*;
-use crate::bit_vectors::{rank_select::tests_utils::test_rank_select_access_for, BitVec};
+use crate::bit_vectors::{
+    rank_select::tests_utils::{
+        assert_rank, assert_select, assert_select0, test_for_words, test_rank_select_access_for,
+    },
+    BitVec,
+};
+use crate::util::BitsRequired;
 const WORD_SIZE: usize = std::mem::size_of::<usize>() * 8;
 #[test]
 fn new() {
@@ -15,7 +21,7 @@ fn new() {
     assert_eq!(rs.superblock_size, 4 * WORD_SIZE);
     assert_eq!(rs.k, 4);
 
-    assert_eq!(rs.superblocks(), &[0, 4, 9, 11]);
+    assert_eq!(rs.superblocks().iter().collect::<Vec<_>>(), [0, 4, 9, 11]);
 }
 
 #[test]
@@ -32,7 +38,7 @@ fn new_when_fully_sampled() {
     assert_eq!(rs.superblock_size, 4 * WORD_SIZE);
     assert_eq!(rs.k, 4);
 
-    assert_eq!(rs.superblocks(), &[0, 4, 9]);
+    assert_eq!(rs.superblocks().iter().collect::<Vec<_>>(), [0, 4, 9]);
 }
 
 macro_rules! test_sparse_sampling_for_k{
@@ -51,7 +57,7 @@ macro_rules! test_sparse_sampling_for_k{
                     assert_eq!(rs.superblock_size, $k * WORD_SIZE);
                     assert_eq!(rs.k, $k);
 
-                    assert_eq!(rs.superblocks(), &[0]);
+                    assert_eq!(rs.superblocks().iter().collect::<Vec<_>>(), [0]);
                 }
 
                 #[test]
@@ -71,7 +77,7 @@ macro_rules! test_sparse_sampling_for_k{
                     let mut acc = 0;
                     for superblock in rs.superblocks().iter().skip(1) {
                         acc += WORD_SIZE * $k;
-                        assert_eq!(*superblock, acc);
+                        assert_eq!(superblock, acc);
                     }
                     assert_eq!(acc, BV_SIZE);
                 }
@@ -92,10 +98,10 @@ macro_rules! test_sparse_sampling_for_k{
                     let mut acc = 0;
                     for superblock in rs.superblocks().iter().skip(1).take(DESIRED_NUM_SUPERBLOCKS) {
                         acc += WORD_SIZE * $k;
-                        assert_eq!(*superblock, acc);
+                        assert_eq!(superblock, acc);
                     }
                     // The last superblock is not fully sampled, as BV_SIZE is not multiple of the superblock size
-                    assert_eq!(rs.superblocks().last().copied(), Some(BV_SIZE));
+                    assert_eq!(rs.superblocks().iter().last(), Some(BV_SIZE));
                     // Do not count the non-multiple block since it has no superblock associated
                     assert_eq!(acc, BV_SIZE - WORD_SIZE / 2);
                 }
@@ -103,10 +109,12 @@ macro_rules! test_sparse_sampling_for_k{
                 fn heap_size_in_bits() {
                     let bv = BitVec::from_value(true,WORD_SIZE * 16 * $k);
                     let rs = SparseSamplingRank::new(&bv, $k);
-                    // size overhead for a bitvec with length n is n/64/k * 64 bits -> n/k bits, which is 1/k of the original size
+                    // size overhead for a bitvec with length n is n/64/k * log(n) bits, as the
+                    // superblocks are stored in a CompactIntVec, which takes whole words
+                    let counter_bits = bv.len().bits_required() as usize;
                     assert_eq!(
                         rs.heap_size_in_bits(),
-                        (bv.len() / WORD_SIZE / $k + 1) * WORD_SIZE
+                        ((bv.len() / WORD_SIZE / $k + 1) * counter_bits).div_ceil(WORD_SIZE) * WORD_SIZE
                     );
                 }
 
@@ -118,3 +126,53 @@ macro_rules! test_sparse_sampling_for_k{
 }
 
 test_sparse_sampling_for_k!(1, 2, 4, 5, 8, 16, 20, 32);
+
+test_for_words!(rank_and_select, |bv| {
+    let rs = SparseSamplingRank::spec(3).build(bv.clone());
+    assert_rank(&bv, &rs);
+    assert_select(&bv, &rs);
+    assert_select0(&bv, &rs);
+});
+
+#[test]
+fn counter_widths() {
+    use crate::bit_vectors::rank_select::{CounterWidth, Rank, Select};
+
+    let bv = (0..10_000)
+        .map(|_| rand::random::<bool>())
+        .collect::<BitVec>();
+    let u64_rs = SparseSamplingRank::spec(2)
+        .with_counter_width(CounterWidth::U64)
+        .build(bv.clone());
+    assert_eq!(
+        u64_rs.rank_support().superblocks().width(),
+        CounterWidth::U64
+    );
+
+    for (width, expected_width, counter_bits) in [
+        (CounterWidth::Auto, CounterWidth::Compact, 14),
+        (CounterWidth::U32, CounterWidth::U32, 32),
+        (CounterWidth::Compact, CounterWidth::Compact, 14),
+    ] {
+        let rs = SparseSamplingRank::spec(2)
+            .with_counter_width(width)
+            .build(bv.clone());
+        let superblocks = rs.rank_support().superblocks();
+        assert_eq!(superblocks.width(), expected_width);
+        assert!(superblocks
+            .iter()
+            .eq(u64_rs.rank_support().superblocks().iter()));
+        assert_eq!(
+            u64_rs.rank_support().heap_size_in_bits() - rs.rank_support().heap_size_in_bits(),
+            superblocks.len() * 64 - superblocks.heap_size_in_bits()
+        );
+        assert!(superblocks.heap_size_in_bits() >= superblocks.len() * counter_bits);
+        assert!(superblocks.heap_size_in_bits() < superblocks.len() * counter_bits + 64);
+
+        for i in 0..=bv.len() {
+            assert_eq!(rs.rank(i), u64_rs.rank(i));
+            assert_eq!(rs.select(i), u64_rs.select(i));
+            assert_eq!(rs.select0(i), u64_rs.select0(i));
+        }
+    }
+}
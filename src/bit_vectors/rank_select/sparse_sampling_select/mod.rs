@@ -1,20 +1,26 @@
-use crate::bit_vectors::rank_select::Rank;
+//! Select data structure that samples the position of every `s`-th 1 and every `s`-th 0.
+//! The samples delimit the superblocks of an inner SparseSamplingRank where the target bit
+//! is, so select only needs to search between two consecutive samples.
+
 use crate::bit_vectors::BitVec;
 use crate::int_vectors::CompactIntVec;
 use crate::profiling::HeapSize;
-use crate::util::{ceil_div, BitsRequired};
+use crate::util::{BitsRequired, Word};
 
-use super::{SelectStructure, SelectSupport, SparseSamplingRank};
+use super::{RankSupport, SelectStructure, SelectSupport, SparseSamplingRank};
 use crate::Build;
+
+#[derive(Debug)]
 pub struct SparseSamplingSelect {
     s: usize,
     total_rank: usize,
     select_samples: CompactIntVec,
+    select0_samples: CompactIntVec,
     sparse_sample_rank: SparseSamplingRank,
 }
 
 impl SparseSamplingSelect {
-    pub fn new(data: &BitVec, s: usize, k: usize) -> Self {
+    pub fn new<W: Word>(data: &BitVec<W>, s: usize, k: usize) -> Self {
         assert!(s > 0, "s must be greater than 0");
         assert!(k > 0, "k must be greater than 0");
 
@@ -22,26 +28,24 @@ impl SparseSamplingSelect {
 
         let bits_required = n.bits_required() as usize;
         let mut select_samples = CompactIntVec::new(bits_required);
+        let mut select0_samples = CompactIntVec::new(bits_required);
 
-        let mut r = 0;
-        for i in 0..n {
-            if data.read(i) {
-                if r % s == 0 {
-                    select_samples.push(i);
-                }
-                r += 1;
-            }
-        }
+        // Positions of the 1s and 0s with rank 1, s+1, 2s+1, ...
+        select_samples.extend(data.iter_ones().step_by(s));
+        select0_samples.extend(data.iter_zeros().step_by(s));
 
         let spec = SparseSamplingRank::spec(k);
         let sparse_sample_rank = spec.build(data);
 
+        // The last sample bounds the search of the ranks after the last sample
         select_samples.push(n);
+        select0_samples.push(n);
 
         Self {
             s,
-            total_rank: r,
+            total_rank: data.count_ones(0..n),
             select_samples,
+            select0_samples,
             sparse_sample_rank,
         }
     }
@@ -49,10 +53,29 @@ impl SparseSamplingSelect {
     pub fn s(&self) -> usize {
         self.s
     }
+
+    pub fn select_samples(&self) -> &CompactIntVec {
+        &self.select_samples
+    }
+
+    pub fn select0_samples(&self) -> &CompactIntVec {
+        &self.select0_samples
+    }
+
+    /// Returns the superblocks of the inner rank structure between which the bit with
+    /// rank `rank` is, according to `samples`.
+    #[inline]
+    fn superblock_hints(&self, samples: &CompactIntVec, rank: usize) -> Option<(usize, usize)> {
+        let superblock_size = self.sparse_sample_rank.superblock_size();
+        let p = (rank - 1) / self.s;
+        let left = samples.get(p)? / superblock_size;
+        let right = samples.get(p + 1)? / superblock_size;
+        Some((left, right))
+    }
 }
 
-impl SelectSupport<BitVec> for SparseSamplingSelect {
-    unsafe fn select(&self, data: &BitVec, rank: usize) -> Option<usize> {
+impl<W: Word> SelectSupport<BitVec<W>> for SparseSamplingSelect {
+    unsafe fn select(&self, data: &BitVec<W>, rank: usize) -> Option<usize> {
         if rank == 0 {
             return Some(0);
         }
@@ -61,16 +84,30 @@ impl SelectSupport<BitVec> for SparseSamplingSelect {
             return None;
         }
 
-        let p = (rank - 1) / self.s;
-        let left = self.select_samples.get(p)? / self.sparse_sample_rank.superblock_size();
-        let right = self.select_samples.get(p + 1)? / self.sparse_sample_rank.superblock_size();
-
+        let (left, right) = self.superblock_hints(&self.select_samples, rank)?;
         self.sparse_sample_rank
             .select_with_hints(data, rank, left, right)
     }
 
-    unsafe fn select0(&self, data: &BitVec, rank0: usize) -> Option<usize> {
-        todo!()
+    unsafe fn select0(&self, data: &BitVec<W>, rank0: usize) -> Option<usize> {
+        if rank0 == 0 {
+            return Some(0);
+        }
+
+        if rank0 > data.len() - self.total_rank {
+            return None;
+        }
+
+        let (left, right) = self.superblock_hints(&self.select0_samples, rank0)?;
+        self.sparse_sample_rank
+            .select0_with_hints(data, rank0, left, right)
+    }
+}
+
+impl<W: Word> RankSupport<BitVec<W>> for SparseSamplingSelect {
+    #[inline]
+    unsafe fn rank(&self, data: &BitVec<W>, index: usize) -> Option<usize> {
+        self.sparse_sample_rank.rank(data, index)
     }
 }
 
@@ -91,37 +128,28 @@ impl SparseSamplingSelect {
     }
 }
 
-impl Build<BitVec, SelectStructure<BitVec, SparseSamplingSelect>> for SparseSamplingSelectSpec {
-    fn build(&self, data: BitVec) -> SelectStructure<BitVec, SparseSamplingSelect> {
+impl<W: Word> Build<BitVec<W>, SelectStructure<BitVec<W>, SparseSamplingSelect>>
+    for SparseSamplingSelectSpec
+{
+    fn build(&self, data: BitVec<W>) -> SelectStructure<BitVec<W>, SparseSamplingSelect> {
         let sparse_sampling_select = SparseSamplingSelect::new(&data, self.s, self.k);
         unsafe { SelectStructure::new(data, sparse_sampling_select) }
     }
 }
 
-impl Build<&BitVec, SparseSamplingSelect> for SparseSamplingSelectSpec {
-    fn build(&self, data: &BitVec) -> SparseSamplingSelect {
+impl<W: Word> Build<&BitVec<W>, SparseSamplingSelect> for SparseSamplingSelectSpec {
+    fn build(&self, data: &BitVec<W>) -> SparseSamplingSelect {
         SparseSamplingSelect::new(data, self.s, self.k)
     }
 }
 
 impl HeapSize for SparseSamplingSelect {
     fn heap_size_in_bits(&self) -> usize {
-        self.select_samples.heap_size_in_bits() + self.sparse_sample_rank.heap_size_in_bits()
+        self.select_samples.heap_size_in_bits()
+            + self.select0_samples.heap_size_in_bits()
+            + self.sparse_sample_rank.heap_size_in_bits()
     }
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::bit_vectors::rank_select::tests_utils::test_select_for;
-    #[test]
-    fn new() {
-        use super::*;
-        let bv = BitVec::from([0b10101010usize; 2]);
-        let ss = SparseSamplingSelect::new(&bv, 4, 1);
-        assert_eq!(ss.s(), 4);
-        println!("{}", ss.select_samples);
-        dbg!(unsafe { ss.select(&bv, 9) });
-    }
-    test_select_for!(SparseSamplingSelect, 4, 4);
-}
+mod tests;
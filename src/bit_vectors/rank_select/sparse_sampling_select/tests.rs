@@ -0,0 +1,66 @@
+use super::*;
+use crate::bit_vectors::rank_select::tests_utils::{
+    assert_rank, assert_select, assert_select0, test_for_words, test_rank_select_access_for,
+};
+
+#[test]
+fn new() {
+    let bv = BitVec::from([0b10101010u8; 2]);
+    let ss = SparseSamplingSelect::new(&bv, 3, 1);
+
+    assert_eq!(ss.s(), 3);
+    // 1s with rank 1, 4, 7 and 0s with rank 1, 4, 7, followed by the length
+    assert_eq!(
+        ss.select_samples().iter().collect::<Vec<_>>(),
+        [1, 7, 13, 16]
+    );
+    assert_eq!(
+        ss.select0_samples().iter().collect::<Vec<_>>(),
+        [0, 6, 12, 16]
+    );
+}
+
+#[test]
+fn new_when_empty_bitvec() {
+    let bv: BitVec = BitVec::new();
+    let ss = SparseSamplingSelect::new(&bv, 4, 4);
+
+    assert_eq!(ss.select_samples().iter().collect::<Vec<_>>(), [0]);
+    assert_eq!(ss.select0_samples().iter().collect::<Vec<_>>(), [0]);
+}
+
+#[test]
+fn heap_size_in_bits() {
+    let bv: BitVec = BitVec::from_value(true, 1000);
+    let ss = SparseSamplingSelect::new(&bv, 10, 4);
+
+    assert_eq!(
+        ss.heap_size_in_bits(),
+        ss.select_samples().heap_size_in_bits()
+            + ss.select0_samples().heap_size_in_bits()
+            + SparseSamplingRank::new(&bv, 4).heap_size_in_bits()
+    );
+}
+
+macro_rules! test_sparse_sampling_select_for {
+    ($(($s:expr, $k:expr)),*) => {
+        $(
+            paste::paste! {
+                mod [<when_s_is_ $s _and_k_is_ $k>] {
+                    use super::*;
+
+                    test_rank_select_access_for!(SparseSamplingSelect, $s, $k);
+                }
+            }
+        )*
+    };
+}
+
+test_sparse_sampling_select_for!((1, 1), (4, 4), (5, 2), (64, 8), (512, 4));
+
+test_for_words!(rank_and_select, |bv| {
+    let rs = SparseSamplingSelect::spec(8, 2).build(bv.clone());
+    assert_rank(&bv, &rs);
+    assert_select(&bv, &rs);
+    assert_select0(&bv, &rs);
+});
@@ -0,0 +1,158 @@
+//! Storage of the absolute ranks of the superblocks of the sampling rank structures.
+//! The ranks are at most the length of the bit vector, so for bit vectors of less than
+//! 2^32 bits a full word per superblock wastes half of the space.
+
+use serde::{Deserialize, Serialize};
+
+use crate::int_vectors::CompactIntVec;
+use crate::profiling::HeapSize;
+use crate::util::BitsRequired;
+
+/// Width of the superblock counters of a rank structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CounterWidth {
+    /// `U32` or `U64` if the length of the bit vector needs more than three quarters of
+    /// their bits, since the compact width would then save less than a quarter of the
+    /// space at the cost of slower accesses, and `Compact` otherwise.
+    #[default]
+    Auto,
+    /// A CompactIntVec with the minimal width to store the length of the bit vector,
+    /// which takes the least space at the cost of slower accesses.
+    Compact,
+    U32,
+    U64,
+}
+
+/// Non-decreasing ranks of the superblocks, stored with a [CounterWidth].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SuperblockCounters {
+    Compact(CompactIntVec),
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+}
+
+impl SuperblockCounters {
+    /// Stores `counters`, whose values are at most `max_value`, with the given width.
+    ///
+    /// # Panics
+    /// If `width` is `U32` and `max_value` does not fit in 32 bits.
+    pub fn new<I>(counters: I, width: CounterWidth, max_value: usize) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let counters = counters.into_iter();
+        let mut result = Self::with_capacity(width, max_value, counters.size_hint().0);
+        result.extend(counters);
+        result
+    }
+
+    /// Creates empty counters for values of at most `max_value`, stored with the given width
+    /// and room for `capacity` counters, so they can be pushed as they are computed.
+    ///
+    /// # Panics
+    /// If `width` is `U32` and `max_value` does not fit in 32 bits.
+    pub fn with_capacity(width: CounterWidth, max_value: usize, capacity: usize) -> Self {
+        let bits_required = max_value.bits_required() as usize;
+        match width {
+            CounterWidth::Auto => {
+                let width = match bits_required {
+                    25..=32 => CounterWidth::U32,
+                    49..=64 => CounterWidth::U64,
+                    _ => CounterWidth::Compact,
+                };
+                Self::with_capacity(width, max_value, capacity)
+            }
+            CounterWidth::Compact => {
+                Self::Compact(CompactIntVec::with_capacity(bits_required, capacity))
+            }
+            CounterWidth::U32 => {
+                assert!(
+                    bits_required <= u32::BITS as usize,
+                    "{max_value} does not fit in a 32-bit counter"
+                );
+                Self::U32(Vec::with_capacity(capacity))
+            }
+            CounterWidth::U64 => Self::U64(Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Appends a counter, which must be at most the `max_value` the counters were created with.
+    #[inline]
+    pub fn push(&mut self, counter: usize) {
+        match self {
+            Self::Compact(counters) => counters.push(counter),
+            Self::U32(counters) => counters.push(counter as u32),
+            Self::U64(counters) => counters.push(counter as u64),
+        }
+    }
+
+    /// Width of the stored counters, which is never `Auto`.
+    #[inline]
+    pub fn width(&self) -> CounterWidth {
+        match self {
+            Self::Compact(_) => CounterWidth::Compact,
+            Self::U32(_) => CounterWidth::U32,
+            Self::U64(_) => CounterWidth::U64,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Compact(counters) => counters.len(),
+            Self::U32(counters) => counters.len(),
+            Self::U64(counters) => counters.len(),
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<usize> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(unsafe { self.get_unchecked(index) })
+    }
+
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> usize {
+        match self {
+            Self::U64(counters) => *counters.get_unchecked(index) as usize,
+            Self::U32(counters) => *counters.get_unchecked(index) as usize,
+            Self::Compact(counters) => counters.get_unchecked(index),
+        }
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).map(|index| unsafe { self.get_unchecked(index) })
+    }
+}
+
+impl Extend<usize> for SuperblockCounters {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for counter in iter {
+            self.push(counter);
+        }
+    }
+}
+
+impl HeapSize for SuperblockCounters {
+    #[inline]
+    fn heap_size_in_bits(&self) -> usize {
+        match self {
+            Self::Compact(counters) => counters.heap_size_in_bits(),
+            Self::U32(counters) => counters.heap_size_in_bits(),
+            Self::U64(counters) => counters.heap_size_in_bits(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,101 @@
+use super::*;
+
+const COUNTERS: [usize; 5] = [0, 3, 3, 700, 1000];
+
+#[test]
+fn new_when_width_is_auto() {
+    // 1000 needs 10 bits, fewer than a 32-bit counter
+    let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Auto, 1000);
+    assert_eq!(counters.width(), CounterWidth::Compact);
+    assert_eq!(
+        counters.heap_size_in_bits(),
+        (COUNTERS.len() * 10).div_ceil(usize::BITS as usize) * usize::BITS as usize
+    );
+
+    // 24 bits save a quarter of a 32-bit counter, 25 bits save less
+    let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Auto, (1 << 24) - 1);
+    assert_eq!(counters.width(), CounterWidth::Compact);
+    let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Auto, 1 << 24);
+    assert_eq!(counters.width(), CounterWidth::U32);
+    assert_eq!(counters.heap_size_in_bits(), COUNTERS.len() * 32);
+
+    let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Auto, u32::MAX as usize);
+    assert_eq!(counters.width(), CounterWidth::U32);
+
+    #[cfg(target_pointer_width = "64")]
+    {
+        let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Auto, 1 << 32);
+        assert_eq!(counters.width(), CounterWidth::Compact);
+
+        let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Auto, (1 << 48) - 1);
+        assert_eq!(counters.width(), CounterWidth::Compact);
+        let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Auto, 1 << 48);
+        assert_eq!(counters.width(), CounterWidth::U64);
+        assert_eq!(counters.heap_size_in_bits(), COUNTERS.len() * 64);
+
+        let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Auto, usize::MAX);
+        assert_eq!(counters.width(), CounterWidth::U64);
+    }
+}
+
+#[test]
+fn with_capacity_and_push() {
+    for width in [
+        CounterWidth::Auto,
+        CounterWidth::Compact,
+        CounterWidth::U32,
+        CounterWidth::U64,
+    ] {
+        let mut counters = SuperblockCounters::with_capacity(width, 1000, COUNTERS.len());
+        assert!(counters.is_empty());
+        for counter in COUNTERS {
+            counters.push(counter);
+        }
+        assert!(counters.iter().eq(COUNTERS));
+    }
+}
+
+#[test]
+fn new_when_width_is_compact() {
+    let counters = SuperblockCounters::new(COUNTERS, CounterWidth::Compact, 1000);
+    assert_eq!(counters.width(), CounterWidth::Compact);
+    // 1000 needs 10 bits, and the CompactIntVec takes whole words
+    assert_eq!(
+        counters.heap_size_in_bits(),
+        (COUNTERS.len() * 10).div_ceil(usize::BITS as usize) * usize::BITS as usize
+    );
+}
+
+#[test]
+fn get() {
+    for width in [
+        CounterWidth::Auto,
+        CounterWidth::Compact,
+        CounterWidth::U32,
+        CounterWidth::U64,
+    ] {
+        let counters = SuperblockCounters::new(COUNTERS, width, 1000);
+        assert_eq!(counters.len(), COUNTERS.len());
+        assert!(!counters.is_empty());
+        for (i, counter) in COUNTERS.iter().enumerate() {
+            assert_eq!(counters.get(i), Some(*counter));
+        }
+        assert_eq!(counters.get(COUNTERS.len()), None);
+        assert!(counters.iter().eq(COUNTERS));
+    }
+}
+
+#[test]
+fn new_when_empty() {
+    let counters = SuperblockCounters::new(Vec::new(), CounterWidth::Compact, 0);
+    assert!(counters.is_empty());
+    assert_eq!(counters.iter().count(), 0);
+    assert_eq!(counters.heap_size_in_bits(), 0);
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+#[should_panic]
+fn new_when_u32_overflows() {
+    SuperblockCounters::new(vec![0, 1 << 32], CounterWidth::U32, 1 << 32);
+}
@@ -1,3 +1,75 @@
+use crate::bit_vectors::rank_select::{Rank, Select};
+use crate::bit_vectors::BitVec;
+use crate::test_utils::random_bools;
+use crate::util::Word;
+
+/// Length of the word fixtures, which is not a multiple of any word size.
+const WORD_FIXTURE_LEN: usize = 10_007;
+
+/// Returns the bit vectors that every structure is checked against for every word type:
+/// empty, all 1s, all 0s, alternating and random.
+pub(crate) fn word_fixtures<W: Word>() -> Vec<BitVec<W>> {
+    vec![
+        BitVec::default(),
+        BitVec::filled(true, WORD_FIXTURE_LEN),
+        BitVec::filled(false, WORD_FIXTURE_LEN),
+        (0..WORD_FIXTURE_LEN).map(|i| i % 2 == 0).collect(),
+        random_bools(WORD_FIXTURE_LEN).into_iter().collect(),
+    ]
+}
+
+/// Checks the rank of `rs` at every position of `bv`, and past its end.
+pub(crate) fn assert_rank<W: Word>(bv: &BitVec<W>, rs: &impl Rank) {
+    let mut rank = 0;
+    for (i, bit) in bv.iter().enumerate() {
+        assert_eq!(rs.rank(i), Some(rank), "rank({i})");
+        rank += bit as usize;
+    }
+    assert_eq!(rs.rank(bv.len()), Some(rank));
+    assert_eq!(rs.rank(bv.len() + 1), None);
+}
+
+/// Checks the select of `rs` for every 1 of `bv`, and past the last one. The positions
+/// are compared with the iterator, as BitVec::select is linear.
+pub(crate) fn assert_select<W: Word>(bv: &BitVec<W>, rs: &impl Select) {
+    assert_eq!(rs.select(0), Some(0));
+    let mut count = 0;
+    for (i, position) in bv.iter_ones().enumerate() {
+        assert_eq!(rs.select(i + 1), Some(position + 1), "select({})", i + 1);
+        count += 1;
+    }
+    assert_eq!(rs.select(count + 1), None);
+}
+
+/// Checks the select0 of `rs` for every 0 of `bv`, and past the last one.
+pub(crate) fn assert_select0<W: Word>(bv: &BitVec<W>, rs: &impl Select) {
+    assert_eq!(rs.select0(0), Some(0));
+    let mut count = 0;
+    for (i, position) in bv.iter_zeros().enumerate() {
+        assert_eq!(rs.select0(i + 1), Some(position + 1), "select0({})", i + 1);
+        count += 1;
+    }
+    assert_eq!(rs.select0(count + 1), None);
+}
+
+/// Generates a test named `$name_when_word_is_<word>` for every word type, which runs
+/// `$body` with `$bv` bound to each of the [word_fixtures] of that word.
+macro_rules! test_for_words {
+    ($name:ident, |$bv:ident| $body:block) => {
+        test_for_words!($name, |$bv| $body, u8, u16, u32, u64, u128);
+    };
+    ($name:ident, |$bv:ident| $body:block, $($w:ty),+) => {
+        $(
+            paste::paste! {
+                #[test]
+                fn [<$name _when_word_is_ $w>]() {
+                    for $bv in crate::bit_vectors::rank_select::tests_utils::word_fixtures::<$w>() $body
+                }
+            }
+        )+
+    };
+}
+
 macro_rules! test_rank_for {
     ($t:ty, $($args:tt)*) => {
         mod rank{
@@ -626,6 +698,7 @@ macro_rules! test_rank_select_access_for{
 }
 
 pub(crate) use test_access_for;
+pub(crate) use test_for_words;
 pub(crate) use test_rank_for;
 pub(crate) use test_rank_select_access_for;
 pub(crate) use test_rank_select_for;
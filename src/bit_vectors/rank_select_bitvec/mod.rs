@@ -0,0 +1,270 @@
+//! Growable bit vector that supports rank and select over the bits appended so far.
+//!
+//! The counters follow the scheme of DenseSamplingRank: every superblock of `k` words
+//! stores the number of 1s before it, and every word stores the number of 1s before it
+//! relative to its superblock. Appending bits only adds the counters of the words that
+//! are completed, and the 1s of the last, incomplete, word are counted on every query.
+
+use crate::int_vectors::CompactIntVec;
+use crate::profiling::HeapSize;
+use crate::util::{bitmask, select_in_word, BitsRequired, Word};
+
+use super::rank_select::{CounterWidth, Rank, Select, SuperblockCounters};
+use super::{Access, BitVec};
+
+#[derive(Debug, Clone)]
+pub struct RankSelectBitVec<W: Word = usize> {
+    data: BitVec<W>,
+    superblocks: SuperblockCounters,
+    blocks: CompactIntVec,
+    k: usize,
+    total_rank: usize,
+}
+
+impl<W: Word> RankSelectBitVec<W> {
+    /// Creates an empty bit vector with superblocks of `k` words.
+    ///
+    /// # Panics
+    /// If `k` is 0.
+    pub fn new(k: usize) -> Self {
+        Self::from_bitvec(BitVec::default(), k)
+    }
+
+    /// Builds the counters of `data`, which can be appended to afterwards.
+    ///
+    /// # Panics
+    /// If `k` is 0.
+    pub fn from_bitvec(data: BitVec<W>, k: usize) -> Self {
+        assert!(k > 0, "k must be greater than 0");
+
+        // This could be superblock_size - block_size, but in practice it is enough with -1
+        let rank_offset_int_width = ((k - 1) * W::BITS).bits_required() as usize;
+        // The bit vector can grow to any length, so the counters must fit any rank
+        let superblocks = SuperblockCounters::with_capacity(
+            CounterWidth::Auto,
+            usize::MAX,
+            data.len() / (k * W::BITS) + 1,
+        );
+        let mut rs = Self {
+            data: BitVec::default(),
+            superblocks,
+            blocks: CompactIntVec::new(rank_offset_int_width),
+            k,
+            total_rank: 0,
+        };
+        // The counters of the first word are there from the start
+        rs.superblocks.push(0);
+        rs.blocks.push(0usize);
+        rs.append(|bitvec| *bitvec = data);
+        rs
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Number of 1s in the bit vector.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.total_rank
+    }
+
+    #[inline]
+    pub fn data(&self) -> &BitVec<W> {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_data(self) -> BitVec<W> {
+        self.data
+    }
+
+    #[inline]
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: bool) {
+        self.append(|data| data.push(value));
+    }
+
+    /// Pushes the `width` lower bits of `value`, as in [BitVec::push_bits].
+    #[inline]
+    pub fn push_bits<T>(&mut self, value: T, width: usize)
+    where
+        T: Into<W>,
+    {
+        self.append(|data| data.push_bits(value, width));
+    }
+
+    /// Appends bits to the data with `append`, and then adds the counters of the words
+    /// that have been completed.
+    fn append<F: FnOnce(&mut BitVec<W>)>(&mut self, append: F) {
+        let old_len = self.data.len();
+        append(&mut self.data);
+        let len = self.data.len();
+        self.total_rank += self.data.count_ones(old_len..len);
+
+        let raw_data = self.data.raw_data();
+        for word in old_len / W::BITS + 1..=len / W::BITS {
+            // The word before `word` is complete now
+            let previous = word - 1;
+            let previous_rank = unsafe {
+                self.superblocks.get_unchecked(previous / self.k)
+                    + self.blocks.get_unchecked(previous)
+            } + raw_data[previous].count_ones() as usize;
+            if word % self.k == 0 {
+                self.superblocks.push(previous_rank);
+                self.blocks.push(0usize);
+            } else {
+                let superblock_rank = unsafe { self.superblocks.get_unchecked(word / self.k) };
+                self.blocks.push(previous_rank - superblock_rank);
+            }
+        }
+    }
+
+    /// Returns the position after the `remaining`-th (1-based) bit of value `VALUE` in the
+    /// word `index`.
+    #[inline]
+    fn select_in_block<const VALUE: bool>(&self, index: usize, remaining: usize) -> usize {
+        let word = self.data.raw_data()[index];
+        let word = if VALUE { word } else { !word };
+        index * W::BITS + select_in_word(word, remaining - 1) + 1
+    }
+}
+
+impl<W: Word> Extend<bool> for RankSelectBitVec<W> {
+    fn extend<T: IntoIterator<Item = bool>>(&mut self, iter: T) {
+        self.append(|data| data.extend(iter));
+    }
+}
+
+impl<'a, W: Word> Extend<&'a bool> for RankSelectBitVec<W> {
+    fn extend<T: IntoIterator<Item = &'a bool>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<W: Word> Rank for RankSelectBitVec<W> {
+    fn rank(&self, index: usize) -> Option<usize> {
+        if index > self.len() {
+            return None;
+        }
+
+        let iw = index / W::BITS;
+        let rank =
+            unsafe { self.superblocks.get_unchecked(iw / self.k) + self.blocks.get_unchecked(iw) };
+        let last_block = self.data.raw_data().get(iw).copied().unwrap_or(W::ZERO);
+        let last_block_target = last_block & bitmask!(W, index % W::BITS, 0);
+        Some(rank + last_block_target.count_ones() as usize)
+    }
+}
+
+impl<W: Word> Select for RankSelectBitVec<W> {
+    fn select(&self, rank: usize) -> Option<usize> {
+        // By definition, select(0) = 0
+        if rank == 0 {
+            return Some(0);
+        }
+        if rank > self.total_rank {
+            return None;
+        }
+
+        // Last superblock, and then last word of the superblock, with less than `rank` 1s
+        // before it. The first one of both always has 0 1s before it.
+        let mut superblock = 0;
+        let mut right = self.superblocks.len() - 1;
+        while superblock < right {
+            let mid = (superblock + right).div_ceil(2);
+            if unsafe { self.superblocks.get_unchecked(mid) } < rank {
+                superblock = mid;
+            } else {
+                right = mid - 1;
+            }
+        }
+        let remaining = rank - unsafe { self.superblocks.get_unchecked(superblock) };
+
+        let first_block = superblock * self.k;
+        let mut left = first_block;
+        let mut right = (first_block + self.k).min(self.blocks.len()) - 1;
+        while left < right {
+            let mid = (left + right).div_ceil(2);
+            if unsafe { self.blocks.get_unchecked(mid) } < remaining {
+                left = mid;
+            } else {
+                right = mid - 1;
+            }
+        }
+
+        let local_rank = remaining - unsafe { self.blocks.get_unchecked(left) };
+        Some(self.select_in_block::<true>(left, local_rank))
+    }
+
+    fn select0(&self, rank0: usize) -> Option<usize> {
+        // By definition, select0(0) = 0
+        if rank0 == 0 {
+            return Some(0);
+        }
+        if rank0 > self.len() - self.total_rank {
+            return None;
+        }
+
+        let superblock_size = self.k * W::BITS;
+        let mut left = 0;
+        let mut right = self.superblocks.len() - 1;
+        while left < right {
+            let mid = (left + right).div_ceil(2);
+            if mid * superblock_size - unsafe { self.superblocks.get_unchecked(mid) } < rank0 {
+                left = mid;
+            } else {
+                right = mid - 1;
+            }
+        }
+        let superblock_rank = unsafe { self.superblocks.get_unchecked(left) };
+        let remaining = rank0 - (left * superblock_size - superblock_rank);
+
+        let first_block = left * self.k;
+        let block_rank0 = |block: usize| {
+            (block - first_block) * W::BITS - unsafe { self.blocks.get_unchecked(block) }
+        };
+        let mut left = first_block;
+        let mut right = (first_block + self.k).min(self.blocks.len()) - 1;
+        while left < right {
+            let mid = (left + right).div_ceil(2);
+            if block_rank0(mid) < remaining {
+                left = mid;
+            } else {
+                right = mid - 1;
+            }
+        }
+
+        // The 0s that pad the last word are after the target 0
+        let local_rank0 = remaining - block_rank0(left);
+        Some(self.select_in_block::<false>(left, local_rank0))
+    }
+}
+
+impl<W: Word> Access for RankSelectBitVec<W> {
+    #[inline]
+    fn access(&self, index: usize) -> Option<bool> {
+        self.data.access(index)
+    }
+}
+
+impl<W: Word> HeapSize for RankSelectBitVec<W> {
+    fn heap_size_in_bits(&self) -> usize {
+        self.data.heap_size_in_bits()
+            + self.superblocks.heap_size_in_bits()
+            + self.blocks.heap_size_in_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,110 @@
+use super::*;
+use crate::bit_vectors::rank_select::tests_utils::{
+    assert_rank, assert_select, assert_select0, test_for_words,
+};
+
+/// Checks every rank, select and select0 of `rs` against the positions of its bits.
+fn assert_rank_select<W: Word>(rs: &RankSelectBitVec<W>) {
+    let data = rs.data();
+    assert_eq!(rs.count_ones(), data.count_ones(0..data.len()));
+    assert_rank(data, rs);
+    assert_select(data, rs);
+    assert_select0(data, rs);
+}
+
+#[test]
+fn new() {
+    let rs: RankSelectBitVec = RankSelectBitVec::new(4);
+
+    assert!(rs.is_empty());
+    assert_eq!(rs.len(), 0);
+    assert_eq!(rs.k(), 4);
+    assert_rank_select(&rs);
+}
+
+#[test]
+fn push() {
+    let mut rs: RankSelectBitVec<u8> = RankSelectBitVec::new(2);
+
+    for i in 0..200 {
+        rs.push(i % 3 == 0 || i % 7 == 0);
+        assert_eq!(rs.len(), i + 1);
+        assert_rank_select(&rs);
+    }
+    assert_eq!(rs.access(3), Some(true));
+    assert_eq!(rs.access(4), Some(false));
+    assert_eq!(rs.access(200), None);
+}
+
+#[test]
+fn push_bits() {
+    let mut rs: RankSelectBitVec = RankSelectBitVec::new(3);
+
+    for i in 0..100 {
+        let width = i % 65;
+        rs.push_bits(rand::random::<usize>() & bitmask!(width, 0), width);
+        assert_rank_select(&rs);
+    }
+}
+
+#[test]
+fn extend() {
+    let mut rs: RankSelectBitVec<u32> = RankSelectBitVec::new(1);
+    let mut expected: BitVec<u32> = BitVec::default();
+
+    for len in [0, 1, 31, 32, 33, 64, 100, 257] {
+        let bits = (0..len).map(|_| rand::random::<bool>()).collect::<Vec<_>>();
+        rs.extend(&bits);
+        expected.extend(&bits);
+        assert_eq!(rs.data(), &expected);
+        assert_rank_select(&rs);
+    }
+}
+
+#[test]
+fn from_bitvec() {
+    let data = (0..1000)
+        .map(|_| rand::random::<bool>())
+        .collect::<BitVec>();
+    let mut rs = RankSelectBitVec::from_bitvec(data.clone(), 2);
+
+    assert_eq!(rs.data(), &data);
+    assert_rank_select(&rs);
+
+    rs.extend([true, false, true]);
+    assert_eq!(rs.len(), 1003);
+    assert_rank_select(&rs);
+    assert_eq!(rs.into_data().len(), 1003);
+}
+
+#[test]
+fn heap_size_in_bits() {
+    let rs = RankSelectBitVec::from_bitvec(BitVec::from_value(true, 64 * 8), 4);
+
+    // 3 superblocks and 9 blocks of 8 bits, including the ones for index == len
+    assert_eq!(
+        rs.heap_size_in_bits(),
+        rs.data().heap_size_in_bits() + 3 * usize::BITS as usize + usize::BITS as usize * 2
+    );
+}
+
+#[test]
+#[should_panic]
+fn new_when_k_is_0() {
+    RankSelectBitVec::<usize>::new(0);
+}
+
+test_for_words!(rank_and_select, |bv| {
+    for k in [1, 2, 5] {
+        let rs = RankSelectBitVec::from_bitvec(bv.clone(), k);
+        assert_rank_select(&rs);
+
+        // The same bits, appended in chunks
+        let mut rs = RankSelectBitVec::new(k);
+        for chunk in bv.iter().collect::<Vec<_>>().chunks(1000) {
+            rs.extend(chunk);
+        }
+        assert_eq!(rs.data(), &bv);
+        assert_rank_select(&rs);
+    }
+});
@@ -0,0 +1,213 @@
+use std::cmp::min;
+
+use super::{binomial, push_wide_bits, DecodeTable, RRRBitVec, MAX_B, MAX_DECODE_TABLE_B};
+use crate::bit_vectors::BitVec;
+use crate::int_vectors::CompactIntVec;
+use crate::util::{bitmask, BitsRequired};
+
+/// Builds a [RRRBitVec] in a single pass from bits that are appended incrementally,
+/// so the input bit vector does not need to be held in memory. Every block is encoded
+/// as soon as it is complete, and the samples are taken on the fly.
+#[derive(Debug, Clone)]
+pub struct RRRBitVecBuilder {
+    b: usize,
+    k: usize,
+    lengths: Vec<usize>,
+    classes: CompactIntVec,
+    offsets: BitVec,
+    // The widths of the samples depend on the totals, so they are compacted when finishing
+    rank_samples: Vec<usize>,
+    offset_samples: Vec<usize>,
+    num_blocks: usize,
+    rank: usize,
+    offset_pos: usize,
+    // Bits of the block that is not complete yet
+    block: u128,
+    block_len: usize,
+    len: usize,
+    decode_table: bool,
+}
+
+impl RRRBitVecBuilder {
+    /// # Panics
+    /// If `b` is 0 or greater than 128, or `k` is 0.
+    pub fn new(b: usize, k: usize) -> Self {
+        assert!(b <= MAX_B, "b must be at most {MAX_B}");
+        assert!(b > 0, "b must be greater than 0");
+        assert!(k > 0, "k must be greater than 0");
+
+        let lengths = (0..=b)
+            .map(|c| (binomial(b, c) - 1).bits_required() as usize)
+            .collect();
+
+        Self {
+            b,
+            k,
+            lengths,
+            classes: CompactIntVec::new(b.bits_required() as usize),
+            offsets: BitVec::new(),
+            rank_samples: Vec::new(),
+            offset_samples: Vec::new(),
+            num_blocks: 0,
+            rank: 0,
+            offset_pos: 0,
+            block: 0,
+            block_len: 0,
+            len: 0,
+            decode_table: false,
+        }
+    }
+
+    /// Builds a decode table when finishing, as in [RRRBitVec::new_with_decode_table].
+    ///
+    /// # Panics
+    /// If `b` is greater than 20, as the table has 2^b entries.
+    pub fn with_decode_table(self) -> Self {
+        assert!(
+            self.b <= MAX_DECODE_TABLE_B,
+            "A decode table can only be built for b at most {MAX_DECODE_TABLE_B}"
+        );
+        Self {
+            decode_table: true,
+            ..self
+        }
+    }
+
+    /// Number of bits appended so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: bool) {
+        self.push_bits(value as usize, 1);
+    }
+
+    /// Appends the `width` lower bits of `value`, in LSB-first order.
+    ///
+    /// # Panics
+    /// If `width` is greater than the width of usize.
+    pub fn push_bits(&mut self, value: usize, width: usize) {
+        assert!(
+            width <= usize::BITS as usize,
+            "Width {width} is greater than the width of usize ({})",
+            usize::BITS
+        );
+
+        let mut value = value as u128 & bitmask!(u128, width, 0);
+        let mut width = width;
+        while width > 0 {
+            let taken = min(width, self.b - self.block_len);
+            self.block |= (value & bitmask!(u128, taken, 0)) << self.block_len;
+            self.block_len += taken;
+            self.len += taken;
+            value >>= taken;
+            width -= taken;
+
+            if self.block_len == self.b {
+                self.push_block();
+            }
+        }
+    }
+
+    /// Appends all the bits of `bitvec`, a word at a time.
+    pub fn extend_from_bitvec(&mut self, bitvec: &BitVec) {
+        let width = BitVec::CONTAINER_WIDTH;
+        for (i, word) in bitvec.raw_data().iter().enumerate() {
+            self.push_bits(*word, min(width, bitvec.len() - i * width));
+        }
+    }
+
+    /// Encodes the current block, padded with 0s if it is not complete.
+    fn push_block(&mut self) {
+        if self.num_blocks.is_multiple_of(self.k) {
+            self.rank_samples.push(self.rank);
+            self.offset_samples.push(self.offset_pos);
+        }
+
+        let (class, offset) = RRRBitVec::encode(self.block, self.b);
+        let offset_size = self.lengths[class];
+        self.classes.push(class);
+        push_wide_bits(&mut self.offsets, offset, offset_size);
+
+        self.rank += class;
+        self.offset_pos += offset_size;
+        self.num_blocks += 1;
+        self.block = 0;
+        self.block_len = 0;
+    }
+
+    pub fn finish(mut self) -> RRRBitVec {
+        if self.block_len > 0 {
+            self.push_block();
+        }
+
+        // For technical reasons, we need to add the total rank as the last superblock (rank sample).
+        // This allows us to access the total rank (altought we store it in the struct),
+        // but also it allows us to perform binary search correctly, because otherwise, we
+        // may perform a sequential search within two superblocks. In this way,
+        // the binary search right position will be always >= the rank we are looking for.
+        // but if we didn't do this, it could be that the last position could be < the rank we are looking for,
+        // and wont be selected as the greatest of the lessers.
+        // We always push that value, since the samples are taken before summing the rank
+        // of the sampled block.
+        self.rank_samples.push(self.rank);
+
+        // After processing all blocks, we may push a last offset sample.
+        if self.num_blocks.is_multiple_of(self.k) {
+            self.offset_samples.push(self.offset_pos);
+        }
+
+        let mut rank_samples = CompactIntVec::with_capacity(
+            self.rank.bits_required() as usize,
+            self.rank_samples.len(),
+        );
+        rank_samples.extend(self.rank_samples);
+        let mut offset_samples = CompactIntVec::with_capacity(
+            self.offset_pos.bits_required() as usize,
+            self.offset_samples.len(),
+        );
+        offset_samples.extend(self.offset_samples);
+
+        RRRBitVec {
+            b: self.b,
+            k: self.k,
+            classes: self.classes,
+            lengths: self.lengths,
+            offsets: self.offsets,
+            offset_samples,
+            rank_samples,
+            total_rank: self.rank,
+            len: self.len,
+            decode_table: self.decode_table.then(|| DecodeTable::shared(self.b)),
+        }
+    }
+}
+
+impl Extend<bool> for RRRBitVecBuilder {
+    fn extend<T: IntoIterator<Item = bool>>(&mut self, iter: T) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a> Extend<&'a bool> for RRRBitVecBuilder {
+    fn extend<T: IntoIterator<Item = &'a bool>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl RRRBitVec {
+    /// Returns a builder that encodes the bits as they are appended.
+    #[inline]
+    pub fn builder(b: usize, k: usize) -> RRRBitVecBuilder {
+        RRRBitVecBuilder::new(b, k)
+    }
+}
@@ -0,0 +1,98 @@
+use std::cmp::min;
+use std::ops::Range;
+
+use super::{push_wide_bits, read_wide_bits_unchecked, RRRBitVec};
+use crate::bit_vectors::BitVec;
+
+impl RRRBitVec {
+    /// Decodes the bits in `range`, block by block, and calls `f` with the bits of every
+    /// block that are in the range, shifted to the start of the value, and their number.
+    /// Every block is decoded once, starting from the closest offset sample.
+    ///
+    /// # Safety
+    /// The range must be in bounds.
+    #[inline]
+    unsafe fn for_each_block_bits<F: FnMut(u128, usize)>(&self, range: Range<usize>, mut f: F) {
+        if range.is_empty() {
+            return;
+        }
+
+        let mut block_index = range.start / self.b;
+        let mut pos = self.offset_position_unchecked(block_index);
+        let mut start = range.start;
+        while start < range.end {
+            let block_start = block_index * self.b;
+            let block_end = min(block_start + self.b, range.end);
+
+            let class = self.classes.get_unchecked(block_index);
+            let class_length = *self.lengths.get_unchecked(class);
+            let offset = read_wide_bits_unchecked(&self.offsets, pos, class_length);
+            // Decode only up to the last bit in the range
+            let block = self.decode_block(class, offset, block_end - block_start);
+            f(block >> (start - block_start), block_end - start);
+
+            pos += class_length;
+            start = block_end;
+            block_index += 1;
+        }
+    }
+
+    /// Returns the `len` bits starting at `index`, with the bit at `index` as the least
+    /// significant bit, as [BitVec::read_bits] does.
+    ///
+    /// # Panics
+    /// If `len` is greater than the width of usize, or the bits are out of bounds.
+    pub fn read_bits(&self, index: usize, len: usize) -> usize {
+        assert!(
+            len <= usize::BITS as usize,
+            "requested len ({len}) is greater than the width of usize ({})",
+            usize::BITS
+        );
+        assert!(
+            index + len <= self.len(),
+            "index out of bounds: the len is {}, but the index is {index} and the width is {len}",
+            self.len()
+        );
+
+        let mut value = 0;
+        let mut width = 0;
+        unsafe {
+            self.for_each_block_bits(index..index + len, |bits, len| {
+                value |= (bits as usize) << width;
+                width += len;
+            });
+        }
+        value
+    }
+
+    /// Returns a BitVec with the bits in `range`.
+    ///
+    /// # Panics
+    /// If the range is out of bounds.
+    pub fn extract(&self, range: Range<usize>) -> BitVec {
+        assert!(
+            range.start <= range.end && range.end <= self.len(),
+            "Cannot extract range {range:?} of a RRRBitVec of length {}",
+            self.len()
+        );
+
+        let mut bitvec = BitVec::with_capacity(range.len());
+        unsafe {
+            self.for_each_block_bits(range, |bits, len| push_wide_bits(&mut bitvec, bits, len));
+        }
+        bitvec
+    }
+
+    /// Decompresses the whole bit vector.
+    #[inline]
+    pub fn to_bitvec(&self) -> BitVec {
+        self.extract(0..self.len())
+    }
+}
+
+impl From<&RRRBitVec> for BitVec {
+    #[inline]
+    fn from(rrr: &RRRBitVec) -> Self {
+        rrr.to_bitvec()
+    }
+}
@@ -0,0 +1,198 @@
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+use super::RRRBitVec;
+use crate::bit_vectors::rank_select::Rank;
+use crate::util::bitmask;
+
+impl RRRBitVec {
+    /// Returns an iterator over the positions of the 1s in the bit vector.
+    pub fn iter_ones(&self) -> IterOnes<'_> {
+        IterOnes::new(self, 0..self.len)
+    }
+
+    /// Returns an iterator over the positions of the 0s in the bit vector.
+    pub fn iter_zeros(&self) -> IterZeros<'_> {
+        IterZeros::new(self, 0..self.len)
+    }
+
+    /// Returns an iterator over the positions of the 1s in the given range of the bit vector.
+    /// The positions are relative to the start of the bit vector, not to the start of the range.
+    pub fn iter_ones_range(&self, range: Range<usize>) -> IterOnes<'_> {
+        IterOnes::new(self, range)
+    }
+
+    /// Returns an iterator over the positions of the 0s in the given range of the bit vector.
+    /// The positions are relative to the start of the bit vector, not to the start of the range.
+    pub fn iter_zeros_range(&self, range: Range<usize>) -> IterZeros<'_> {
+        IterZeros::new(self, range)
+    }
+}
+
+/// Iterator over the positions of the bits with value `VALUE` in a range of a [`RRRBitVec`].
+/// Each block is decoded only once, and the positions inside it are found by counting
+/// trailing (or leading, when iterating from the back) zeros.
+pub struct IterBits<'a, const VALUE: bool> {
+    rrr: &'a RRRBitVec,
+    front_block: usize,
+    // Position in `offsets` where the offset of the front block starts
+    front_pos: usize,
+    // Bits of the front block that have not been yielded yet
+    front_word: u128,
+    back_block: usize,
+    // Position in `offsets` where the offset of the back block starts
+    back_pos: usize,
+    // Bits of the back block that have not been yielded yet. When the front and back
+    // blocks are the same, all the pending bits are stored in `front_word`.
+    back_word: u128,
+    remaining: usize,
+}
+
+pub type IterOnes<'a> = IterBits<'a, true>;
+pub type IterZeros<'a> = IterBits<'a, false>;
+
+impl<'a, const VALUE: bool> IterBits<'a, VALUE> {
+    pub fn new(rrr: &'a RRRBitVec, range: Range<usize>) -> Self {
+        assert!(
+            range.start <= range.end && range.end <= rrr.len(),
+            "Cannot iterate range {range:?} of a RRRBitVec of length {}",
+            rrr.len()
+        );
+
+        if range.is_empty() {
+            return Self {
+                rrr,
+                front_block: 0,
+                front_pos: 0,
+                front_word: 0,
+                back_block: 0,
+                back_pos: 0,
+                back_word: 0,
+                remaining: 0,
+            };
+        }
+
+        let b = rrr.b();
+        let front_block = range.start / b;
+        let back_block = (range.end - 1) / b;
+        let front_pos = unsafe { rrr.offset_position_unchecked(front_block) };
+        let back_pos = unsafe { rrr.offset_position_unchecked(back_block) };
+
+        // Keep the bits at positions >= start in the first block, and < end in the last block
+        let front_mask = !bitmask!(u128, range.start % b, 0);
+        let back_mask = bitmask!(u128, range.end - back_block * b, 0);
+
+        let (front_word, back_word) = if front_block == back_block {
+            let word = Self::load(rrr, front_block, front_pos) & front_mask & back_mask;
+            (word, 0)
+        } else {
+            (
+                Self::load(rrr, front_block, front_pos) & front_mask,
+                Self::load(rrr, back_block, back_pos) & back_mask,
+            )
+        };
+
+        let ones = rrr.rank(range.end).unwrap() - rrr.rank(range.start).unwrap();
+        let remaining = if VALUE {
+            ones
+        } else {
+            range.end - range.start - ones
+        };
+
+        Self {
+            rrr,
+            front_block,
+            front_pos,
+            front_word,
+            back_block,
+            back_pos,
+            back_word,
+            remaining,
+        }
+    }
+
+    /// Decodes the block `block_index`, with the bits we look for set to 1.
+    #[inline]
+    fn load(rrr: &RRRBitVec, block_index: usize, pos: usize) -> u128 {
+        let block = unsafe { rrr.decode_block_unchecked(block_index, pos) };
+        if VALUE {
+            block
+        } else {
+            !block & bitmask!(u128, rrr.b(), 0)
+        }
+    }
+
+    #[inline]
+    fn class_length(&self, block_index: usize) -> usize {
+        unsafe {
+            let class = self.rrr.classes().get_unchecked(block_index);
+            *self.rrr.lengths().get_unchecked(class)
+        }
+    }
+}
+
+impl<const VALUE: bool> Iterator for IterBits<'_, VALUE> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front_word == 0 {
+            if self.front_block >= self.back_block {
+                return None;
+            }
+            self.front_pos += self.class_length(self.front_block);
+            self.front_block += 1;
+            self.front_word = if self.front_block == self.back_block {
+                std::mem::take(&mut self.back_word)
+            } else {
+                Self::load(self.rrr, self.front_block, self.front_pos)
+            };
+        }
+
+        let bit_index = self.front_word.trailing_zeros() as usize;
+        // clear the lowest set bit
+        self.front_word &= self.front_word - 1;
+        self.remaining -= 1;
+        Some(self.front_block * self.rrr.b() + bit_index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<const VALUE: bool> DoubleEndedIterator for IterBits<'_, VALUE> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // When both ends reached the same block, the pending bits are in `front_word`
+        loop {
+            if self.front_block == self.back_block || self.back_word != 0 {
+                break;
+            }
+            self.back_block -= 1;
+            self.back_pos -= self.class_length(self.back_block);
+            if self.back_block != self.front_block {
+                self.back_word = Self::load(self.rrr, self.back_block, self.back_pos);
+            }
+        }
+
+        let word = if self.front_block == self.back_block {
+            &mut self.front_word
+        } else {
+            &mut self.back_word
+        };
+
+        if *word == 0 {
+            return None;
+        }
+
+        let bit_index = u128::BITS as usize - 1 - word.leading_zeros() as usize;
+        *word ^= 1 << bit_index;
+        self.remaining -= 1;
+        Some(self.back_block * self.rrr.b() + bit_index)
+    }
+}
+
+impl<const VALUE: bool> ExactSizeIterator for IterBits<'_, VALUE> {}
+
+impl<const VALUE: bool> FusedIterator for IterBits<'_, VALUE> {}
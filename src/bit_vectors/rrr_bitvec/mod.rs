@@ -1,9 +1,16 @@
+use std::cmp::min;
+use std::sync::{Arc, OnceLock};
+
 use super::{
     rank_select::{Rank, Select},
     Access, BitVec,
 };
 use crate::Build;
-use crate::{int_vectors::CompactIntVec, profiling::HeapSize, util::BitsRequired};
+use crate::{
+    int_vectors::CompactIntVec,
+    profiling::HeapSize,
+    util::{bitmask, select_in_word},
+};
 
 #[derive(Debug, Clone)]
 pub struct RRRBitVec {
@@ -20,18 +27,90 @@ pub struct RRRBitVec {
     // total rank is useful for select operation
     total_rank: usize,
     len: usize,
+    // Shared by every structure with the same b
+    decode_table: Option<Arc<DecodeTable>>,
+}
+
+/// Maximum block size for which a decode table can be built, as it has 2^b entries.
+const MAX_DECODE_TABLE_B: usize = 20;
+
+/// Table with every block of `b` bits, sorted by class and then by offset, so a
+/// block is decoded with a single lookup instead of walking its offset bit by bit.
+#[derive(Debug, Clone)]
+struct DecodeTable {
+    blocks: CompactIntVec,
+    // Index of the first block of every class
+    class_starts: Vec<usize>,
+}
+
+impl DecodeTable {
+    /// Returns the table of `b`, which is built on its first use and then shared by every
+    /// structure with the same `b`.
+    fn shared(b: usize) -> Arc<Self> {
+        static TABLES: [OnceLock<Arc<DecodeTable>>; MAX_DECODE_TABLE_B + 1] =
+            [const { OnceLock::new() }; MAX_DECODE_TABLE_B + 1];
+
+        assert!(
+            b <= MAX_DECODE_TABLE_B,
+            "A decode table can only be built for b at most {MAX_DECODE_TABLE_B}"
+        );
+        Arc::clone(TABLES[b].get_or_init(|| Arc::new(Self::new(b))))
+    }
+
+    fn new(b: usize) -> Self {
+        let mut class_starts = Vec::with_capacity(b + 1);
+        let mut start = 0;
+        for class in 0..=b {
+            class_starts.push(start);
+            start += binomial(b, class) as usize;
+        }
+
+        // The blocks are generated already sorted, by decoding every offset of every class
+        let mut blocks = CompactIntVec::with_capacity(b, 1 << b);
+        for class in 0..=b {
+            for offset in 0..binomial(b, class) {
+                blocks.push(RRRBitVec::decode(class, offset, b, b) as usize);
+            }
+        }
+
+        Self {
+            blocks,
+            class_starts,
+        }
+    }
+
+    /// # Safety
+    /// The class and offset must be the ones of a block of `b` bits.
+    #[inline]
+    unsafe fn decode_unchecked(&self, class: usize, offset: u128) -> u128 {
+        let index = self.class_starts.get_unchecked(class) + offset as usize;
+        self.blocks.get_unchecked(index) as u128
+    }
+}
+
+impl HeapSize for DecodeTable {
+    #[inline]
+    fn heap_size_in_bits(&self) -> usize {
+        self.blocks.heap_size_in_bits() + self.class_starts.heap_size_in_bits()
+    }
 }
 
-const N: usize = std::mem::size_of::<usize>() * 8;
-static BINOMIALS: [[usize; N + 1]; N + 1] = get_binomial_table();
+/// Maximum block size. Blocks, and offsets, are encoded in a u128.
+const MAX_B: usize = u128::BITS as usize;
+
+/// Maximum block size whose blocks and offsets fit in a u64, which covers the usual
+/// block sizes and keeps their arithmetic, and the binomial table, narrow.
+const MAX_NARROW_B: usize = u64::BITS as usize;
+static BINOMIALS: [[u64; MAX_NARROW_B + 1]; MAX_NARROW_B + 1] = get_binomial_table();
 
-// Precompute binomial table at compile time
-const fn get_binomial_table() -> [[usize; N + 1]; N + 1] {
-    let mut binomials = [[0; N + 1]; N + 1];
+// Precompute binomial table at compile time. The greatest value, 64 choose 32,
+// is smaller than 2^63, so it fits in a u64.
+const fn get_binomial_table() -> [[u64; MAX_NARROW_B + 1]; MAX_NARROW_B + 1] {
+    let mut binomials = [[0; MAX_NARROW_B + 1]; MAX_NARROW_B + 1];
 
     let mut i = 0;
     // Initialize diagonal
-    while i <= N {
+    while i <= MAX_NARROW_B {
         binomials[i][i] = 1;
         i += 1;
     }
@@ -39,7 +118,7 @@ const fn get_binomial_table() -> [[usize; N + 1]; N + 1] {
 
     // Initialize first column
     i = 0;
-    while i <= N {
+    while i <= MAX_NARROW_B {
         binomials[i][0] = 1;
         i += 1;
     }
@@ -47,7 +126,7 @@ const fn get_binomial_table() -> [[usize; N + 1]; N + 1] {
     let mut n = 1;
     let mut k = 1;
 
-    while n <= N {
+    while n <= MAX_NARROW_B {
         while k < n {
             binomials[n][k] = binomials[n - 1][k - 1] + binomials[n - 1][k];
             k += 1;
@@ -59,166 +138,200 @@ const fn get_binomial_table() -> [[usize; N + 1]; N + 1] {
     binomials
 }
 
-impl RRRBitVec {
-    #[inline]
-    // TODO: Array K as precomputed binomials
-    fn encode(block: usize, b: usize) -> (usize, usize) {
-        let class = block.count_ones() as usize;
-        let mut offset = 0;
-        let mut current_class = class;
-        let mut current_block = block;
-        for i in 1..=b {
-            // if there are no more 1s or all of the remaining bits are 1s
-            if current_class == 0 || current_class > (b - i) {
-                break;
-            }
-
-            // index bit starting from the right
-            if current_block & 0b1 == 0b1 {
-                offset += BINOMIALS[b - i][current_class];
-                current_class -= 1;
+/// Binomials up to 128 choose 128, for blocks of more than 64 bits. The greatest value,
+/// 128 choose 64, is smaller than 2^125, so it fits in a u128. As they are rarely
+/// needed, they are computed the first time they are used instead of at compile time.
+fn wide_binomials() -> &'static [[u128; MAX_B + 1]] {
+    static WIDE_BINOMIALS: OnceLock<Vec<[u128; MAX_B + 1]>> = OnceLock::new();
+    WIDE_BINOMIALS.get_or_init(|| {
+        let mut binomials = vec![[0; MAX_B + 1]; MAX_B + 1];
+        binomials[0][0] = 1;
+        for n in 1..=MAX_B {
+            let previous = binomials[n - 1];
+            let row = &mut binomials[n];
+            row[0] = 1;
+            for k in 1..=n {
+                row[k] = previous[k - 1] + previous[k];
             }
-            current_block >>= 1;
         }
+        binomials
+    })
+}
 
-        (class, offset)
+/// Number of blocks of `b` bits with `class` 1s, which is the number of offsets of
+/// that class.
+#[inline]
+fn binomial(b: usize, class: usize) -> u128 {
+    if b <= MAX_NARROW_B {
+        BINOMIALS[b][class] as u128
+    } else {
+        wide_binomials()[b][class]
     }
+}
 
-    // Optimization to decode only len bits
-    #[inline]
-    fn decode(class: usize, offset: usize, b: usize, len: usize) -> usize {
-        if len == 0 {
-            return 0;
-        }
-        if class == b {
-            // The block is all ones
-            return usize::MAX >> (usize::BITS - len as u32);
-        } else if class == 0 {
-            // The block is all zeros
-            return 0;
+/// Defines the functions to encode and decode blocks whose bits and offsets are
+/// stored in `$int`, with the binomials of `$binomials`.
+macro_rules! block_coding {
+    ($encode:ident, $decode:ident, $int:ty, $binomials:expr) => {
+        #[inline]
+        fn $encode(block: $int, b: usize) -> (usize, $int) {
+            let binomials = $binomials;
+            let class = block.count_ones() as usize;
+            let mut offset = 0;
+            let mut current_class = class;
+            let mut current_block = block;
+            for i in 1..=b {
+                // if there are no more 1s or all of the remaining bits are 1s
+                if current_class == 0 || current_class > (b - i) {
+                    break;
+                }
+
+                // index bit starting from the right
+                if current_block & 0b1 == 0b1 {
+                    offset += binomials[b - i][current_class];
+                    current_class -= 1;
+                }
+                current_block >>= 1;
+            }
+
+            (class, offset)
         }
 
-        let mut block = 0;
-        let mut current_class = class;
-        let mut current_offset = offset;
-        let mut i = 0;
-        while current_class > 1 {
-            if i >= len {
-                return block;
+        // Optimization to decode only len bits
+        #[inline]
+        fn $decode(class: usize, offset: $int, b: usize, len: usize) -> $int {
+            if len == 0 {
+                return 0;
             }
-            let num_previous_offsets = BINOMIALS[b - i - 1][current_class];
-            if current_offset >= num_previous_offsets {
-                // If the bit at ith position (starting from the right) is set,
-                // there are b-i-1 choose current_class combinations that
-                // have a 0 in that position and precede that offset
-
-                // setbits!(block, 1, i, 1); optimized
-                block |= 1 << i;
-                current_offset -= num_previous_offsets;
-                current_class -= 1;
+            if class == b {
+                // The block is all ones
+                return <$int>::MAX >> (<$int>::BITS - len as u32);
+            } else if class == 0 {
+                // The block is all zeros
+                return 0;
             }
-            i += 1;
-        }
 
-        // In case the class is 1, the bit at position b - offset - 1 is set.
-        // since there are only b possible combinations.s
-        if current_class > 0 {
-            let bit_offset = b - current_offset - 1;
-            if bit_offset < len {
-                block |= 1 << bit_offset;
+            let binomials = $binomials;
+            let mut block = 0;
+            let mut current_class = class;
+            let mut current_offset = offset;
+            let mut i = 0;
+            while current_class > 1 {
+                if i >= len {
+                    return block;
+                }
+                let num_previous_offsets = binomials[b - i - 1][current_class];
+                if current_offset >= num_previous_offsets {
+                    // If the bit at ith position (starting from the right) is set,
+                    // there are b-i-1 choose current_class combinations that
+                    // have a 0 in that position and precede that offset
+
+                    // setbits!(block, 1, i, 1); optimized
+                    block |= 1 << i;
+                    current_offset -= num_previous_offsets;
+                    current_class -= 1;
+                }
+                i += 1;
             }
+
+            // In case the class is 1, the bit at position b - offset - 1 is set.
+            // since there are only b possible combinations.s
+            if current_class > 0 {
+                let bit_offset = b - current_offset as usize - 1;
+                if bit_offset < len {
+                    block |= 1 << bit_offset;
+                }
+            }
+
+            block
         }
+    };
+}
+
+block_coding!(encode_narrow, decode_narrow, u64, &BINOMIALS);
+block_coding!(encode_wide, decode_wide, u128, wide_binomials());
+
+/// Reads `len` bits at `index` of `bitvec`, where `len` may be greater than the
+/// container width.
+///
+/// # Safety
+/// The bits must be in bounds.
+#[inline]
+unsafe fn read_wide_bits_unchecked(bitvec: &BitVec, index: usize, len: usize) -> u128 {
+    if len <= BitVec::CONTAINER_WIDTH {
+        return bitvec.read_bits_unchecked(index, len) as u128;
+    }
+    let mut value = 0;
+    for offset in (0..len).step_by(BitVec::CONTAINER_WIDTH) {
+        let width = min(len - offset, BitVec::CONTAINER_WIDTH);
+        value |= (bitvec.read_bits_unchecked(index + offset, width) as u128) << offset;
+    }
+    value
+}
 
-        block
+/// Pushes the `len` lower bits of `value` into `bitvec`, where `len` may be greater
+/// than the container width.
+#[inline]
+fn push_wide_bits(bitvec: &mut BitVec, value: u128, len: usize) {
+    for offset in (0..len).step_by(BitVec::CONTAINER_WIDTH) {
+        let width = min(len - offset, BitVec::CONTAINER_WIDTH);
+        bitvec.push_bits((value >> offset) as usize, width);
     }
+}
 
+impl RRRBitVec {
     #[inline]
-    pub fn new(bitvec: BitVec, b: usize, k: usize) -> Self {
-        // TODO: allow for greater b values? Now it corresponds
-        // to the size of usize (which is also the size of BitVec::CONTAINER_WIDTH)
-        assert!(b <= N, "b must be at most {}", N);
-        assert!(b > 0, "b must be greater than 0");
-        assert!(k > 0, "k must be greater than 0");
-
-        // TODO: use Vec<usize> instead of this compact vec for lenghts? Using compact int vec
-        // is useful since we know the max value the vector would hold, but acces
-        // may be slower..
-        // We know the max value the vector would hold(log log (b choose b/2)) so we can use compact int vec
-        // for b =64, if we use Vec<usize>, that would use 4096 bits, but with compact int vec, we use 384 bits
-
-        let len = bitvec.len();
-
-        // let mut lengths = CompactIntVec::with_capacity(compact_vec_width, b + 1);
-        let mut lengths = Vec::with_capacity(b + 1);
-
-        lengths.extend((0..=b).map(|c| (BINOMIALS[b][c] - 1).bits_required() as usize));
-        let lengths = lengths;
-
-        let blocks = CompactIntVec::from_raw_parts(bitvec, b);
-        let mut total_offsets_size = 0;
-        let mut total_rank = 0;
-        for block in blocks.iter() {
-            let class = block.count_ones() as usize;
-            total_offsets_size += unsafe { lengths.get_unchecked(class) };
-            total_rank += class;
+    // TODO: Array K as precomputed binomials
+    fn encode(block: u128, b: usize) -> (usize, u128) {
+        if b <= MAX_NARROW_B {
+            let (class, offset) = encode_narrow(block as u64, b);
+            (class, offset as u128)
+        } else {
+            encode_wide(block, b)
         }
+    }
 
-        let classes_width = b.bits_required() as usize;
-        let mut classes = CompactIntVec::with_capacity(classes_width, blocks.len());
-        let mut offsets = BitVec::with_capacity(total_offsets_size);
+    #[inline]
+    fn decode(class: usize, offset: u128, b: usize, len: usize) -> u128 {
+        if b <= MAX_NARROW_B {
+            decode_narrow(class, offset as u64, b, len) as u128
+        } else {
+            decode_wide(class, offset, b, len)
+        }
+    }
 
-        let mut rank_samples =
-            CompactIntVec::with_capacity(total_rank.bits_required() as usize, blocks.len() / k + 1);
-        let mut offset_samples = CompactIntVec::with_capacity(
-            total_offsets_size.bits_required() as usize,
-            blocks.len() / k + 1,
-        );
+    /// Builds the structure from the words of `bitvec`. To build it without holding the
+    /// whole bit vector in memory, use [RRRBitVec::builder].
+    #[inline]
+    pub fn new(bitvec: BitVec, b: usize, k: usize) -> Self {
+        let mut builder = RRRBitVecBuilder::new(b, k);
+        builder.extend_from_bitvec(&bitvec);
+        builder.finish()
+    }
 
-        let mut current_rank = 0;
-        let mut current_offset_pos = 0;
+    /// Builds the structure along with a table of the 2^b blocks of `b` bits, which
+    /// decodes blocks with a single lookup. The table is built once per `b`, and shared
+    /// by every structure with that `b`.
+    ///
+    /// # Panics
+    /// If `b` is greater than 20, in addition to the cases of [RRRBitVec::new].
+    pub fn new_with_decode_table(bitvec: BitVec, b: usize, k: usize) -> Self {
+        // The builder checks b before encoding any block
+        let mut builder = RRRBitVecBuilder::new(b, k).with_decode_table();
+        builder.extend_from_bitvec(&bitvec);
+        builder.finish()
+    }
 
-        for (idx, block) in blocks.iter().enumerate() {
-            if idx % k == 0 {
-                rank_samples.push(current_rank);
-                offset_samples.push(current_offset_pos);
+    /// Decodes the first `len` bits of the block with the given class and offset,
+    /// with the decode table if there is one.
+    #[inline]
+    fn decode_block(&self, class: usize, offset: u128, len: usize) -> u128 {
+        match &self.decode_table {
+            Some(table) => {
+                let block = unsafe { table.decode_unchecked(class, offset) };
+                block & bitmask!(u128, len, 0)
             }
-            let (class, offset) = Self::encode(block, b);
-            let offset_size = unsafe { lengths.get_unchecked(class) };
-            classes.push(class);
-            offsets.push_bits(offset, *offset_size);
-
-            current_rank += class;
-            current_offset_pos += offset_size;
-        }
-
-        // For technical reasons, we need to add the total rank as the last superblock (rank sample).
-        // This allows us to access the total rank (altought we store it in the struct),
-        // but also it allows us to perform binary search correctly, because otherwise, we
-        // may perform a sequential search within two superblocks. In this way,
-        // the binary search right position will be always >= the rank we are looking for.
-        // but if we didn't do this, it could be that the last position could be < the rank we are looking for,
-        // and wont be selected as the greatest of the lessers.
-        // It is not necessary to add the total rank if all the blocks are fully sampled by a superblock, since the
-        // last superblock will be the total rank.
-        // We always push that value, since at the end of the for loop, we always have at least one block not sampled
-        // (by the if idx % k == 0 condition being checked before summing the rank)
-        rank_samples.push(current_rank);
-
-        // After processing all blocks, we may push a last offset sample.
-        if blocks.len() % k == 0 {
-            offset_samples.push(current_offset_pos);
-        }
-
-        Self {
-            b,
-            k,
-            classes,
-            offsets,
-            lengths,
-            rank_samples,
-            offset_samples,
-            total_rank: current_rank,
-            len,
+            None => Self::decode(class, offset, self.b, len),
         }
     }
 
@@ -243,25 +356,45 @@ impl RRRBitVec {
             return class > 0;
         }
 
-        let is = block_index / self.k;
-        let sampled_pos = self.offset_samples.get_unchecked(is);
-
-        let mut pos = sampled_pos;
-        for i in is * self.k..block_index {
-            let c = self.classes.get_unchecked(i);
-            pos += self.lengths.get_unchecked(c);
-        }
+        let pos = self.offset_position_unchecked(block_index);
 
         let len = self.lengths.get_unchecked(class);
-        let offset = self.offsets.read_bits_unchecked(pos, *len);
+        let offset = read_wide_bits_unchecked(&self.offsets, pos, *len);
 
         let bit_offset = i % self.b;
-        let block = Self::decode(class, offset, self.b, bit_offset + 1);
+        let block = self.decode_block(class, offset, bit_offset + 1);
         // Optimize this as we know we are reading 1 bit
         // getbits!(block, 1, bit_offset)
         block >> bit_offset & 0b1 == 0b1
     }
 
+    /// Returns the position in `offsets` where the offset of the block `block_index` starts.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds block index is undefined behavior
+    #[inline]
+    pub(crate) unsafe fn offset_position_unchecked(&self, block_index: usize) -> usize {
+        let is = block_index / self.k;
+        let mut pos = self.offset_samples.get_unchecked(is);
+        for i in is * self.k..block_index {
+            let c = self.classes.get_unchecked(i);
+            pos += self.lengths.get_unchecked(c);
+        }
+        pos
+    }
+
+    /// Decodes the whole block `block_index`, whose offset starts at position `pos` in `offsets`.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds block index or a wrong position is undefined behavior
+    #[inline]
+    pub(crate) unsafe fn decode_block_unchecked(&self, block_index: usize, pos: usize) -> u128 {
+        let class = self.classes.get_unchecked(block_index);
+        let offset =
+            read_wide_bits_unchecked(&self.offsets, pos, *self.lengths.get_unchecked(class));
+        self.decode_block(class, offset, self.b)
+    }
+
     #[inline]
     pub fn b(&self) -> usize {
         self.b
@@ -297,6 +430,12 @@ impl RRRBitVec {
         &self.offset_samples
     }
 
+    /// Whether blocks are decoded with a table instead of bit by bit.
+    #[inline]
+    pub fn has_decode_table(&self) -> bool {
+        self.decode_table.is_some()
+    }
+
     #[inline]
     pub fn total_rank(&self) -> usize {
         self.total_rank
@@ -313,6 +452,8 @@ impl RRRBitVec {
 }
 
 impl HeapSize for RRRBitVec {
+    /// Includes the decode table in full, even though it is shared with the other
+    /// structures with the same `b`.
     #[inline]
     fn heap_size_in_bits(&self) -> usize {
         self.classes.heap_size_in_bits()
@@ -320,6 +461,10 @@ impl HeapSize for RRRBitVec {
             + self.lengths.heap_size_in_bits()
             + self.rank_samples.heap_size_in_bits()
             + self.offset_samples.heap_size_in_bits()
+            + self
+                .decode_table
+                .as_ref()
+                .map_or(0, |table| table.heap_size_in_bits())
     }
 }
 
@@ -354,10 +499,9 @@ impl Rank for RRRBitVec {
         let last_block = self.classes.get(iw).map_or(0, |last_class| {
             let block_offset = index % self.b;
             let last_offset = unsafe {
-                self.offsets
-                    .read_bits_unchecked(p, *self.lengths.get_unchecked(last_class))
+                read_wide_bits_unchecked(&self.offsets, p, *self.lengths.get_unchecked(last_class))
             };
-            Self::decode(last_class, last_offset, self.b, block_offset)
+            self.decode_block(last_class, last_offset, block_offset)
         });
 
         let last_block_rank = last_block.count_ones() as usize;
@@ -409,19 +553,12 @@ impl Select for RRRBitVec {
 
         // at this point, we are exactly that the block that contains the rank is `block_index`
         let class_length = unsafe { *self.lengths.get_unchecked(class) };
-        let offset = unsafe { self.offsets.read_bits_unchecked(local_pos, class_length) };
+        let offset = unsafe { read_wide_bits_unchecked(&self.offsets, local_pos, class_length) };
 
-        let mut block = Self::decode(class, offset, self.b, self.b);
+        let block = self.decode_block(class, offset, self.b);
 
         // select the bit in the block
-        let mut bit_index = 0;
-        while local_rank < rank {
-            if block & 0b1 == 0b1 {
-                local_rank += 1;
-            }
-            block >>= 1;
-            bit_index += 1;
-        }
+        let bit_index = select_in_word(block, rank - local_rank - 1) + 1;
 
         Some(block_index * self.b + bit_index)
     }
@@ -468,19 +605,12 @@ impl Select for RRRBitVec {
 
         // at this point, we are exactly that the block that contains the rank is `block_index`
         let class_length = unsafe { *self.lengths.get_unchecked(class) };
-        let offset = unsafe { self.offsets.read_bits_unchecked(local_pos, class_length) };
+        let offset = unsafe { read_wide_bits_unchecked(&self.offsets, local_pos, class_length) };
 
-        let mut block = Self::decode(class, offset, self.b, self.b);
+        let block = self.decode_block(class, offset, self.b);
 
         // select the bit in the block
-        let mut bit_index = 0;
-        while local_rank0 < rank0 {
-            if block & 0b1 == 0b0 {
-                local_rank0 += 1;
-            }
-            block >>= 1;
-            bit_index += 1;
-        }
+        let bit_index = select_in_word(!block, rank0 - local_rank0 - 1) + 1;
 
         Some(block_index * self.b + bit_index)
     }
@@ -499,12 +629,34 @@ impl Access for RRRBitVec {
 pub struct RRRBitVecSpec {
     pub b: usize,
     pub k: usize,
+    pub decode_table: bool,
 }
 
 impl RRRBitVecSpec {
     #[inline]
     pub const fn new(b: usize, k: usize) -> Self {
-        Self { b, k }
+        Self {
+            b,
+            k,
+            decode_table: false,
+        }
+    }
+
+    /// Decodes the blocks with a table of the 2^b blocks of `b` bits, which is faster
+    /// for small block sizes.
+    ///
+    /// # Panics
+    /// If `b` is greater than 20, as the table has 2^b entries.
+    #[inline]
+    pub const fn with_decode_table(self) -> Self {
+        assert!(
+            self.b <= MAX_DECODE_TABLE_B,
+            "A decode table can only be built for b at most 20"
+        );
+        Self {
+            decode_table: true,
+            ..self
+        }
     }
 }
 
@@ -518,9 +670,22 @@ impl RRRBitVec {
 impl Build<BitVec, RRRBitVec> for RRRBitVecSpec {
     #[inline]
     fn build(&self, data: BitVec) -> RRRBitVec {
-        RRRBitVec::new(data, self.b, self.k)
+        if self.decode_table {
+            RRRBitVec::new_with_decode_table(data, self.b, self.k)
+        } else {
+            RRRBitVec::new(data, self.b, self.k)
+        }
     }
 }
 
+pub mod builder;
+mod extract;
+pub mod iter;
+mod serialize;
+mod text;
+
+pub use builder::RRRBitVecBuilder;
+pub use text::ParseRRRBitVecError;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,270 @@
+//! Serialization of [RRRBitVec].
+//!
+//! The serialized form stores the parameters, the classes, the offsets and the samples,
+//! along with a format version. The lengths of the offsets are derived from `b`, so they
+//! are rebuilt on load. Of the decode table only its presence is stored, and the shared
+//! table of `b` is used on load.
+//! The bits are stored in u64 words on every target, so a structure serialized on a
+//! 64-bit target can be loaded on a 32-bit one, and the other way around.
+//! Everything else is validated on load, so a corrupted or crafted input is rejected
+//! with an error instead of producing a structure that answers queries wrong, or panics.
+
+use std::cmp::min;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    binomial, read_wide_bits_unchecked, DecodeTable, RRRBitVec, MAX_B, MAX_DECODE_TABLE_B,
+};
+use crate::bit_vectors::BitVec;
+use crate::int_vectors::CompactIntVec;
+use crate::util::{bitmask, BitsRequired, Word};
+
+/// Version of the serialized form, increased whenever the layout changes.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "RRRBitVec")]
+struct SerializedRRRBitVec {
+    version: u32,
+    b: usize,
+    k: usize,
+    len: usize,
+    total_rank: usize,
+    classes: CompactIntVec<u64>,
+    offsets: BitVec<u64>,
+    rank_samples: CompactIntVec<u64>,
+    offset_samples: CompactIntVec<u64>,
+    decode_table: bool,
+}
+
+impl Serialize for RRRBitVec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedRRRBitVec {
+            version: FORMAT_VERSION,
+            b: self.b,
+            k: self.k,
+            len: self.len,
+            total_rank: self.total_rank,
+            classes: to_u64_ints(&self.classes),
+            offsets: to_u64_words(&self.offsets),
+            rank_samples: to_u64_ints(&self.rank_samples),
+            offset_samples: to_u64_ints(&self.offset_samples),
+            decode_table: self.decode_table.is_some(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RRRBitVec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SerializedRRRBitVec::deserialize(deserializer)?
+            .validate()
+            .map_err(D::Error::custom)
+    }
+}
+
+fn to_u64_words(bitvec: &BitVec) -> BitVec<u64> {
+    let width = BitVec::CONTAINER_WIDTH;
+    let mut words = BitVec::with_bit_capacity(bitvec.len());
+    for (i, word) in bitvec.raw_data().iter().enumerate() {
+        words.push_bits(*word as u64, min(width, bitvec.len() - i * width));
+    }
+    words
+}
+
+fn to_u64_ints(values: &CompactIntVec) -> CompactIntVec<u64> {
+    let mut ints = CompactIntVec::with_width_and_capacity(values.width(), values.len());
+    ints.extend(values.iter().map(|value| value as u64));
+    ints
+}
+
+/// Converts the words back to usize, once checked with [check_bitvec].
+fn from_u64_words(words: &BitVec<u64>) -> BitVec {
+    let width = BitVec::CONTAINER_WIDTH;
+    let mut bitvec = BitVec::with_capacity(words.len());
+    for (i, word) in words.raw_data().iter().enumerate() {
+        let word_len = min(u64::BITS as usize, words.len() - i * u64::BITS as usize);
+        for offset in (0..word_len).step_by(width) {
+            bitvec.push_bits((word >> offset) as usize, min(width, word_len - offset));
+        }
+    }
+    bitvec
+}
+
+/// Converts the values back to usize, once checked with [check_compact_int_vec].
+fn from_u64_ints(ints: &CompactIntVec<u64>) -> CompactIntVec {
+    let mut values = CompactIntVec::with_capacity(ints.width(), ints.len());
+    values.extend(ints.iter().map(|value| value as usize));
+    values
+}
+
+/// Checks that the words of `bitvec` match its length, so reading within it cannot go
+/// out of bounds, and that the bits past its length are 0s, as BitVec relies on them.
+fn check_bitvec<W: Word>(bitvec: &BitVec<W>, name: &str) -> Result<(), String> {
+    let words = bitvec.raw_data().len();
+    if words != bitvec.len().div_ceil(W::BITS) {
+        return Err(format!(
+            "{name} has {words} words for a length of {} bits",
+            bitvec.len()
+        ));
+    }
+    let used_bits = bitvec.len() % W::BITS;
+    if let Some(last) = bitvec.raw_data().last() {
+        if used_bits != 0 && *last & !bitmask!(W, used_bits, 0) != W::ZERO {
+            return Err(format!("{name} has 1s past its length"));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the bits of `values` match its length and width, and that the width fits
+/// in a usize.
+fn check_compact_int_vec(values: &CompactIntVec<u64>, name: &str) -> Result<(), String> {
+    check_bitvec(values.raw_data(), name)?;
+    if values.width() > BitVec::CONTAINER_WIDTH
+        || values.raw_data().len() != values.len() * values.width()
+    {
+        return Err(format!(
+            "{name} has {} bits for {} values of {} bits",
+            values.raw_data().len(),
+            values.len(),
+            values.width()
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `samples` holds exactly the `expected` samples.
+fn check_samples(
+    samples: &CompactIntVec<u64>,
+    expected: &[usize],
+    name: &str,
+) -> Result<(), String> {
+    check_compact_int_vec(samples, name)?;
+    if samples.len() != expected.len() {
+        return Err(format!(
+            "{name} has {} values, expected {}",
+            samples.len(),
+            expected.len()
+        ));
+    }
+    if let Some(index) = samples
+        .iter()
+        .zip(expected)
+        .position(|(v, e)| v != *e as u64)
+    {
+        return Err(format!("{name} has a wrong value at index {index}"));
+    }
+    Ok(())
+}
+
+impl SerializedRRRBitVec {
+    fn validate(self) -> Result<RRRBitVec, String> {
+        if self.version != FORMAT_VERSION {
+            return Err(format!(
+                "unsupported format version {}, expected {FORMAT_VERSION}",
+                self.version
+            ));
+        }
+        let b = self.b;
+        if b == 0 || b > MAX_B {
+            return Err(format!("b must be between 1 and {MAX_B}, got {b}"));
+        }
+        if self.k == 0 {
+            return Err("k must be greater than 0".to_string());
+        }
+        if self.decode_table && b > MAX_DECODE_TABLE_B {
+            return Err(format!(
+                "a decode table can only be built for b at most {MAX_DECODE_TABLE_B}, got {b}"
+            ));
+        }
+
+        // The lengths are not stored, they only depend on b
+        let lengths = (0..=b)
+            .map(|c| (binomial(b, c) - 1).bits_required() as usize)
+            .collect::<Vec<_>>();
+
+        check_compact_int_vec(&self.classes, "classes")?;
+        check_bitvec(&self.offsets, "offsets")?;
+        let classes = from_u64_ints(&self.classes);
+        let offsets = from_u64_words(&self.offsets);
+        let num_blocks = self.len.div_ceil(b);
+        if classes.len() != num_blocks {
+            return Err(format!(
+                "classes has {} values, expected {num_blocks} blocks of {b} bits",
+                classes.len()
+            ));
+        }
+
+        // Walk the blocks as the builder does, recomputing the samples
+        let mut rank_samples = Vec::with_capacity(num_blocks / self.k + 2);
+        let mut offset_samples = Vec::with_capacity(num_blocks / self.k + 2);
+        let mut rank = 0;
+        let mut pos = 0;
+        for (i, class) in classes.iter().enumerate() {
+            if i % self.k == 0 {
+                rank_samples.push(rank);
+                offset_samples.push(pos);
+            }
+            if class > b {
+                return Err(format!("block {i} has class {class}, greater than b"));
+            }
+
+            let offset_size = lengths[class];
+            if pos + offset_size > offsets.len() {
+                return Err(format!("offsets is too short for block {i}"));
+            }
+            // Safety: just checked that the offset is in bounds
+            let offset = unsafe { read_wide_bits_unchecked(&offsets, pos, offset_size) };
+            if offset >= binomial(b, class) {
+                return Err(format!(
+                    "block {i} has an offset out of range for its class"
+                ));
+            }
+            // The last block is padded with 0s
+            if i == num_blocks - 1 && !self.len.is_multiple_of(b) {
+                let block = RRRBitVec::decode(class, offset, b, b);
+                if block & !bitmask!(u128, self.len % b, 0) != 0 {
+                    return Err("the padding of the last block has 1s".to_string());
+                }
+            }
+
+            rank += class;
+            pos += offset_size;
+        }
+        rank_samples.push(rank);
+        if num_blocks.is_multiple_of(self.k) {
+            offset_samples.push(pos);
+        }
+
+        if pos != offsets.len() {
+            return Err(format!(
+                "offsets has {} bits, expected {pos}",
+                offsets.len()
+            ));
+        }
+        if rank != self.total_rank {
+            return Err(format!(
+                "total rank is {}, but the classes add up to {rank}",
+                self.total_rank
+            ));
+        }
+        check_samples(&self.rank_samples, &rank_samples, "rank samples")?;
+        check_samples(&self.offset_samples, &offset_samples, "offset samples")?;
+
+        Ok(RRRBitVec {
+            b,
+            k: self.k,
+            classes,
+            lengths,
+            offsets,
+            offset_samples: from_u64_ints(&self.offset_samples),
+            rank_samples: from_u64_ints(&self.rank_samples),
+            total_rank: self.total_rank,
+            len: self.len,
+            decode_table: self.decode_table.then(|| DecodeTable::shared(b)),
+        })
+    }
+}
@@ -0,0 +1,186 @@
+use super::*;
+use crate::test_utils::random_bitvec;
+use crate::util::BitsRequired;
+
+/// Encodes `bitvec` in two passes over its blocks, as RRRBitVec::new did before it used
+/// the builder: the first one computes the totals to size the samples, and the second
+/// one encodes the blocks and takes the samples.
+fn reference(bitvec: &BitVec, b: usize, k: usize) -> RRRBitVec {
+    let len = bitvec.len();
+    let lengths = (0..=b)
+        .map(|c| (binomial(b, c) - 1).bits_required() as usize)
+        .collect::<Vec<_>>();
+
+    // The last block is padded with 0s
+    let num_blocks = len.div_ceil(b);
+    let blocks = || {
+        (0..num_blocks)
+            .map(|i| unsafe { read_wide_bits_unchecked(bitvec, i * b, min(b, len - i * b)) })
+    };
+    let mut total_offsets_size = 0;
+    let mut total_rank = 0;
+    for block in blocks() {
+        let class = block.count_ones() as usize;
+        total_offsets_size += lengths[class];
+        total_rank += class;
+    }
+
+    let mut classes = CompactIntVec::new(b.bits_required() as usize);
+    let mut offsets = BitVec::new();
+    let mut rank_samples = CompactIntVec::new(total_rank.bits_required() as usize);
+    let mut offset_samples = CompactIntVec::new(total_offsets_size.bits_required() as usize);
+    let mut rank = 0;
+    let mut offset_pos = 0;
+    for (i, block) in blocks().enumerate() {
+        if i % k == 0 {
+            rank_samples.push(rank);
+            offset_samples.push(offset_pos);
+        }
+        let (class, offset) = RRRBitVec::encode(block, b);
+        classes.push(class);
+        push_wide_bits(&mut offsets, offset, lengths[class]);
+        rank += class;
+        offset_pos += lengths[class];
+    }
+    rank_samples.push(rank);
+    if num_blocks % k == 0 {
+        offset_samples.push(offset_pos);
+    }
+
+    RRRBitVec {
+        b,
+        k,
+        classes,
+        lengths,
+        offsets,
+        offset_samples,
+        rank_samples,
+        total_rank,
+        len,
+        decode_table: None,
+    }
+}
+
+fn assert_identical(rrr: &RRRBitVec, expected: &RRRBitVec) {
+    assert_eq!(rrr.b(), expected.b());
+    assert_eq!(rrr.k(), expected.k());
+    assert_eq!(rrr.len(), expected.len());
+    assert_eq!(rrr.total_rank(), expected.total_rank());
+    assert_eq!(rrr.lengths(), expected.lengths());
+    assert_eq!(rrr.offsets(), expected.offsets());
+    assert_eq!(rrr.classes().to_string(), expected.classes().to_string());
+    assert_eq!(
+        rrr.rank_samples().to_string(),
+        expected.rank_samples().to_string()
+    );
+    assert_eq!(
+        rrr.offset_samples().to_string(),
+        expected.offset_samples().to_string()
+    );
+}
+
+#[test]
+fn push() {
+    let bitvec = (0..1000)
+        .map(|_| rand::random::<bool>())
+        .collect::<BitVec>();
+
+    for (b, k) in [
+        (1, 1),
+        (4, 4),
+        (15, 32),
+        (63, 2),
+        (64, 3),
+        (100, 5),
+        (128, 4),
+    ] {
+        let mut builder = RRRBitVec::builder(b, k);
+        for i in 0..bitvec.len() {
+            builder.push(bitvec.access(i).unwrap());
+        }
+        assert_eq!(builder.len(), bitvec.len());
+        assert_identical(&builder.finish(), &reference(&bitvec, b, k));
+    }
+}
+
+#[test]
+fn push_bits() {
+    let mut bitvec: BitVec = BitVec::new();
+    let mut builder = RRRBitVec::builder(31, 4);
+
+    for i in 0..200 {
+        let width = i % 65;
+        let value = rand::random::<usize>() & bitmask!(width, 0);
+        bitvec.push_bits(value, width);
+        builder.push_bits(value, width);
+    }
+    assert_identical(&builder.finish(), &reference(&bitvec, 31, 4));
+}
+
+#[test]
+fn extend() {
+    let bits = (0..777).map(|_| rand::random::<bool>()).collect::<Vec<_>>();
+    let mut builder = RRRBitVec::builder(20, 8);
+
+    // The input arrives in chunks, e.g. from a reader
+    for chunk in bits.chunks(100) {
+        builder.extend(chunk);
+    }
+    let expected = reference(&bits.iter().copied().collect(), 20, 8);
+    assert_identical(&builder.finish(), &expected);
+}
+
+#[test]
+fn extend_from_bitvec() {
+    let mut builder = RRRBitVec::builder(15, 32);
+    let mut bitvec: BitVec = BitVec::new();
+
+    for len in [0, 1, 63, 64, 65, 1000] {
+        let chunk = (0..len).map(|_| rand::random::<bool>()).collect::<BitVec>();
+        builder.extend_from_bitvec(&chunk);
+        bitvec.extend(chunk.iter());
+    }
+    assert_identical(&builder.finish(), &reference(&bitvec, 15, 32));
+}
+
+#[test]
+fn finish_when_empty() {
+    let builder = RRRBitVec::builder(15, 32);
+    assert!(builder.is_empty());
+
+    let rrr = builder.finish();
+    assert_identical(&rrr, &reference(&BitVec::new(), 15, 32));
+    assert_eq!(rrr.rank(0), Some(0));
+    assert_eq!(rrr.select(1), None);
+}
+
+#[test]
+fn with_decode_table() {
+    let bitvec = (0..1000)
+        .map(|_| rand::random::<bool>())
+        .collect::<BitVec>();
+    let mut builder = RRRBitVec::builder(15, 32).with_decode_table();
+    builder.extend_from_bitvec(&bitvec);
+    let rrr = builder.finish();
+
+    assert!(rrr.has_decode_table());
+    assert_identical(&rrr, &reference(&bitvec, 15, 32));
+    assert!(rrr.iter_ones().eq(bitvec.iter_ones()));
+}
+
+#[test]
+fn new_is_the_same_as_reference() {
+    let bitvec = random_bitvec(1000);
+    for (b, k) in [(1, 1), (15, 32), (64, 3), (128, 4)] {
+        assert_identical(
+            &RRRBitVec::new(bitvec.clone(), b, k),
+            &reference(&bitvec, b, k),
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn with_decode_table_when_b_is_too_big() {
+    RRRBitVec::builder(21, 32).with_decode_table();
+}
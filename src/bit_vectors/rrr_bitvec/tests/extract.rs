@@ -0,0 +1,97 @@
+use super::*;
+use crate::test_utils::random_bitvec;
+
+#[test]
+fn to_bitvec() {
+    for (b, k) in [(1, 1), (3, 2), (15, 32), (64, 4), (100, 3), (128, 2)] {
+        for len in [0, 1, 99, 1000] {
+            let bitvec = random_bitvec(len);
+            let rrr = RRRBitVec::new(bitvec.clone(), b, k);
+
+            assert_eq!(rrr.to_bitvec(), bitvec);
+            assert_eq!(BitVec::from(&rrr), bitvec);
+        }
+    }
+}
+
+#[test]
+fn to_bitvec_with_decode_table() {
+    let bitvec = random_bitvec(1000);
+    let rrr = RRRBitVec::new_with_decode_table(bitvec.clone(), 15, 4);
+
+    assert_eq!(rrr.to_bitvec(), bitvec);
+}
+
+#[test]
+fn to_bitvec_when_blocks_are_uniform() {
+    // Blocks of class 0 and class b have no offset
+    let mut bitvec = BitVec::from_value(false, 300);
+    bitvec.extend(std::iter::repeat_n(true, 300));
+    let rrr = RRRBitVec::new(bitvec.clone(), 31, 2);
+
+    assert_eq!(rrr.to_bitvec(), bitvec);
+}
+
+#[test]
+fn extract() {
+    let bitvec = random_bitvec(500);
+
+    for b in [1, 7, 15, 64, 127] {
+        let rrr = RRRBitVec::new(bitvec.clone(), b, 3);
+        for start in (0..=500).step_by(37) {
+            for end in (start..=500).step_by(41) {
+                let expected = (start..end)
+                    .map(|i| bitvec.access(i).unwrap())
+                    .collect::<BitVec>();
+                assert_eq!(rrr.extract(start..end), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn read_bits() {
+    let bitvec = random_bitvec(1000);
+
+    for b in [1, 5, 15, 63, 64, 65, 128] {
+        let rrr = RRRBitVec::new(bitvec.clone(), b, 4);
+        for index in (0..1000).step_by(13) {
+            for len in [0, 1, 2, 17, 63, 64] {
+                if index + len > bitvec.len() {
+                    continue;
+                }
+                assert_eq!(rrr.read_bits(index, len), bitvec.read_bits(index, len));
+            }
+        }
+    }
+}
+
+#[test]
+fn re_encode_with_another_spec() {
+    let bitvec = random_bitvec(2000);
+    let rrr = RRRBitVec::spec(15, 32).build(bitvec.clone());
+    let re_encoded = RRRBitVec::spec(63, 8).build(rrr.to_bitvec());
+
+    assert!(re_encoded.iter_ones().eq(bitvec.iter_ones()));
+}
+
+#[test]
+#[should_panic]
+fn extract_when_out_of_bounds() {
+    let rrr = RRRBitVec::new(random_bitvec(100), 15, 4);
+    rrr.extract(50..101);
+}
+
+#[test]
+#[should_panic]
+fn read_bits_when_out_of_bounds() {
+    let rrr = RRRBitVec::new(random_bitvec(100), 15, 4);
+    rrr.read_bits(90, 11);
+}
+
+#[test]
+#[should_panic]
+fn read_bits_when_len_is_too_big() {
+    let rrr = RRRBitVec::new(random_bitvec(1000), 15, 4);
+    rrr.read_bits(0, usize::BITS as usize + 1);
+}
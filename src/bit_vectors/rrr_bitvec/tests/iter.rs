@@ -0,0 +1,81 @@
+use super::*;
+use crate::test_utils::random_bitvec;
+
+macro_rules! test_iter_for_b {
+    ($($b:expr),*) => {
+        $(
+            paste::paste! {
+                mod [<when_b_is_ $b>] {
+                    use super::*;
+                    const B: usize = $b;
+
+                    #[test]
+                    fn iter_ones_and_zeros() {
+                        let bitvec = random_bitvec(1000);
+                        let rrr = RRRBitVec::new(bitvec.clone(), B, 4);
+
+                        assert_eq!(rrr.iter_ones().collect::<Vec<_>>(), bitvec.iter_ones().collect::<Vec<_>>());
+                        assert_eq!(rrr.iter_zeros().collect::<Vec<_>>(), bitvec.iter_zeros().collect::<Vec<_>>());
+                        assert_eq!(rrr.iter_ones().len(), bitvec.iter_ones().len());
+                        assert_eq!(rrr.iter_zeros().len(), bitvec.iter_zeros().len());
+                    }
+
+                    #[test]
+                    fn iter_ones_and_zeros_when_all_ones() {
+                        let bitvec = BitVec::from_value(true, 1000);
+                        let rrr = RRRBitVec::new(bitvec, B, 4);
+
+                        assert_eq!(rrr.iter_ones().collect::<Vec<_>>(), (0..1000).collect::<Vec<_>>());
+                        assert_eq!(rrr.iter_zeros().next(), None);
+                    }
+
+                    #[test]
+                    fn iter_ones_and_zeros_range() {
+                        let bitvec = random_bitvec(1000);
+                        let rrr = RRRBitVec::new(bitvec.clone(), B, 4);
+
+                        for (start, end) in [(0, 0), (3, 5), (60, 70), (64, 128), (100, 901), (0, 1000)] {
+                            assert_eq!(
+                                rrr.iter_ones_range(start..end).collect::<Vec<_>>(),
+                                bitvec.iter_ones_range(start..end).collect::<Vec<_>>()
+                            );
+                            assert_eq!(
+                                rrr.iter_zeros_range(start..end).rev().collect::<Vec<_>>(),
+                                bitvec.iter_zeros_range(start..end).rev().collect::<Vec<_>>()
+                            );
+                        }
+                    }
+
+                    #[test]
+                    fn iter_ones_alternating_ends() {
+                        let bitvec = random_bitvec(1000);
+                        let rrr = RRRBitVec::new(bitvec.clone(), B, 4);
+
+                        let mut iter = rrr.iter_ones_range(10..990);
+                        let mut expected = bitvec.iter_ones_range(10..990);
+                        loop {
+                            assert_eq!(iter.len(), expected.len());
+                            let next = iter.next();
+                            assert_eq!(next, expected.next());
+                            let next_back = iter.next_back();
+                            assert_eq!(next_back, expected.next_back());
+                            if next.is_none() || next_back.is_none() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+test_iter_for_b!(1, 3, 15, 31, 63, 64);
+
+#[test]
+fn iter_ones_when_empty() {
+    let rrr = RRRBitVec::new(BitVec::new(), 15, 4);
+
+    assert_eq!(rrr.iter_ones().next(), None);
+    assert_eq!(rrr.iter_zeros().next_back(), None);
+}
@@ -1,5 +1,12 @@
 use super::*;
 use crate::bit_vectors::rank_select::tests_utils::test_rank_select_access_for;
+use crate::test_utils::random_bitvec;
+
+mod builder;
+mod extract;
+mod iter;
+mod serialize;
+mod text;
 
 #[test]
 fn encode() {
@@ -28,14 +35,38 @@ fn decode() {
 #[test]
 fn decode_is_the_inverse_of_encode() {
     let mut block = 0;
-    for b in 1..=64 {
-        block |= (1 << (b - 1)) * (b % 2);
+    for b in 1..=128 {
+        block |= (1 << (b - 1)) * (b % 2) as u128;
         let (class, offset) = RRRBitVec::encode(block, b);
         let decoded_block = RRRBitVec::decode(class, offset, b, b);
         assert_eq!(block, decoded_block);
     }
 }
 
+#[test]
+fn narrow_and_wide_binomials_match() {
+    let wide = wide_binomials();
+    for (b, row) in wide.iter().enumerate().take(MAX_NARROW_B + 1) {
+        for (class, value) in row.iter().enumerate().take(b + 1) {
+            assert_eq!(binomial(b, class), *value);
+        }
+    }
+    assert_eq!(binomial(128, 64), wide[128][64]);
+}
+
+#[test]
+fn encode_and_decode_around_narrow_b() {
+    for b in [MAX_NARROW_B - 1, MAX_NARROW_B, MAX_NARROW_B + 1] {
+        for _ in 0..100 {
+            let block = rand::random::<u128>() & bitmask!(u128, b, 0);
+            let (class, offset) = RRRBitVec::encode(block, b);
+            assert_eq!(class, block.count_ones() as usize);
+            assert!(offset < binomial(b, class));
+            assert_eq!(RRRBitVec::decode(class, offset, b, b), block);
+        }
+    }
+}
+
 #[test]
 fn new() {
     let b = 4;
@@ -155,4 +186,112 @@ macro_rules! test_for_b {
     }
 }
 
-test_for_b!(1, 2, 3, 4, 15, 16, 20, 31, 32, 63, 64);
+test_for_b!(1, 2, 3, 4, 15, 16, 20, 31, 32, 63, 64, 65, 100, 127, 128);
+
+#[test]
+fn new_when_b_is_greater_than_64() {
+    // Low-entropy bit vector, with a 1 every 100 bits
+    let bitvec = (0..10_000).map(|i| i % 100 == 0).collect::<BitVec>();
+    let rrr = RRRBitVec::new(bitvec.clone(), 127, 8);
+
+    // The offsets of the blocks with a single 1 take 7 bits
+    assert_eq!(rrr.lengths()[1], 7);
+    // 128 choose 64 needs 125 bits, which span more than one container word
+    assert_eq!(RRRBitVec::new(bitvec.clone(), 128, 8).lengths()[64], 125);
+
+    for i in 0..bitvec.len() {
+        assert_eq!(rrr.access(i), bitvec.access(i));
+    }
+    for (rank, position) in bitvec.iter_ones().enumerate() {
+        assert_eq!(rrr.select(rank + 1), Some(position + 1));
+        assert_eq!(rrr.rank(position + 1), Some(rank + 1));
+    }
+    assert!(rrr.iter_ones().eq(bitvec.iter_ones()));
+}
+
+#[test]
+fn new_when_blocks_have_wide_offsets() {
+    // Blocks with half of their bits set have the widest offsets
+    let bitvec = (0..5_000)
+        .map(|_| rand::random::<bool>())
+        .collect::<BitVec>();
+    let rrr = RRRBitVec::new(bitvec.clone(), 128, 4);
+
+    assert!(rrr.iter_ones().eq(bitvec.iter_ones()));
+    assert!(rrr.iter_zeros().rev().eq(bitvec.iter_zeros().rev()));
+    for (rank0, position) in bitvec.iter_zeros().enumerate() {
+        assert_eq!(rrr.select0(rank0 + 1), Some(position + 1));
+    }
+}
+
+#[test]
+#[should_panic]
+fn new_when_b_is_greater_than_128() {
+    RRRBitVec::new(BitVec::from_value(true, 1000), 129, 4);
+}
+
+#[test]
+fn decode_table() {
+    let bitvec = (0..3_000)
+        .map(|_| rand::random::<bool>())
+        .collect::<BitVec>();
+
+    for b in [1, 2, 3, 7, 15, 16, 20] {
+        let rrr = RRRBitVec::spec(b, 8).build(bitvec.clone());
+        let table_rrr = RRRBitVec::spec(b, 8)
+            .with_decode_table()
+            .build(bitvec.clone());
+        assert!(!rrr.has_decode_table());
+        assert!(table_rrr.has_decode_table());
+
+        for i in 0..bitvec.len() {
+            assert_eq!(table_rrr.access(i), rrr.access(i));
+        }
+        for i in 0..=bitvec.len() {
+            assert_eq!(table_rrr.rank(i), rrr.rank(i));
+            assert_eq!(table_rrr.select(i), rrr.select(i));
+            assert_eq!(table_rrr.select0(i), rrr.select0(i));
+        }
+        assert!(table_rrr.iter_ones().eq(bitvec.iter_ones()));
+
+        // The table has 2^b blocks of b bits
+        let table_size = table_rrr.heap_size_in_bits() - rrr.heap_size_in_bits();
+        assert!(table_size >= (1 << b) * b);
+    }
+}
+
+#[test]
+fn decode_table_heap_size_in_bits() {
+    let bitvec = BitVec::from_value(true, 1000);
+    let rrr = RRRBitVec::new(bitvec.clone(), 15, 32);
+    let table_rrr = RRRBitVec::new_with_decode_table(bitvec, 15, 32);
+
+    // 2^15 blocks of 15 bits, and the first block of every class
+    assert_eq!(
+        table_rrr.heap_size_in_bits(),
+        rrr.heap_size_in_bits() + (1 << 15) * 15 + 16 * usize::BITS as usize
+    );
+}
+
+#[test]
+fn decode_table_is_shared() {
+    let first = RRRBitVec::new_with_decode_table(random_bitvec(1000), 11, 32);
+    let second = RRRBitVec::new_with_decode_table(random_bitvec(100), 11, 4);
+    let other_b = RRRBitVec::new_with_decode_table(random_bitvec(100), 12, 4);
+
+    let table = |rrr: &RRRBitVec| Arc::clone(rrr.decode_table.as_ref().unwrap());
+    assert!(Arc::ptr_eq(&table(&first), &table(&second)));
+    assert!(!Arc::ptr_eq(&table(&first), &table(&other_b)));
+}
+
+#[test]
+#[should_panic]
+fn decode_table_when_b_is_too_big() {
+    RRRBitVec::new_with_decode_table(BitVec::from_value(true, 1000), 21, 32);
+}
+
+#[test]
+#[should_panic]
+fn spec_with_decode_table_when_b_is_too_big() {
+    RRRBitVec::spec(21, 32).with_decode_table();
+}
@@ -0,0 +1,208 @@
+use serde_json::Value;
+
+use super::*;
+use crate::test_utils::random_bitvec;
+
+fn to_value(rrr: &RRRBitVec) -> Value {
+    serde_json::to_value(rrr).unwrap()
+}
+
+fn from_value(value: Value) -> Result<RRRBitVec, serde_json::Error> {
+    serde_json::from_value(value)
+}
+
+#[test]
+fn round_trip() {
+    for (b, k) in [(1, 1), (3, 2), (15, 32), (64, 4), (100, 3), (128, 2)] {
+        for len in [0, 1, 99, 1000] {
+            let bitvec = random_bitvec(len);
+            let rrr = RRRBitVec::new(bitvec.clone(), b, k);
+
+            let json = serde_json::to_string(&rrr).unwrap();
+            let loaded: RRRBitVec = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(loaded.b(), b);
+            assert_eq!(loaded.k(), k);
+            assert_eq!(loaded.len(), len);
+            assert_eq!(loaded.total_rank(), rrr.total_rank());
+            assert_eq!(loaded.lengths(), rrr.lengths());
+            assert_eq!(loaded.offsets(), rrr.offsets());
+            assert!(!loaded.has_decode_table());
+            assert_eq!(loaded.to_bitvec(), bitvec);
+            for i in 0..=rrr.total_rank() {
+                assert_eq!(loaded.select(i), rrr.select(i));
+            }
+        }
+    }
+}
+
+#[test]
+fn round_trip_with_decode_table() {
+    let bitvec = random_bitvec(1000);
+    let rrr = RRRBitVec::new_with_decode_table(bitvec.clone(), 15, 4);
+
+    let value = to_value(&rrr);
+    // Only the presence of the table is stored
+    assert_eq!(value["decode_table"], Value::Bool(true));
+    let loaded = from_value(value).unwrap();
+
+    assert!(loaded.has_decode_table());
+    // The table is not rebuilt, but shared with the original
+    assert!(Arc::ptr_eq(
+        loaded.decode_table.as_ref().unwrap(),
+        rrr.decode_table.as_ref().unwrap()
+    ));
+    assert_eq!(loaded.heap_size_in_bits(), rrr.heap_size_in_bits());
+    assert_eq!(loaded.to_bitvec(), bitvec);
+}
+
+#[test]
+fn lengths_are_not_stored() {
+    let rrr = RRRBitVec::new(random_bitvec(100), 15, 4);
+    let value = to_value(&rrr);
+
+    assert!(value.get("lengths").is_none());
+    assert_eq!(value["version"], 1);
+}
+
+#[test]
+fn load_fails_when_parameters_are_invalid() {
+    let rrr = RRRBitVec::new(random_bitvec(100), 15, 4);
+
+    for (field, invalid) in [
+        ("version", Value::from(2)),
+        ("b", Value::from(0)),
+        ("b", Value::from(129)),
+        ("b", Value::from(14)),
+        ("k", Value::from(0)),
+        ("k", Value::from(5)),
+        ("len", Value::from(106)),
+        ("total_rank", Value::from(rrr.total_rank() + 1)),
+    ] {
+        let mut value = to_value(&rrr);
+        value[field] = invalid;
+        assert!(from_value(value).is_err(), "{field}");
+    }
+}
+
+#[test]
+fn load_fails_when_decode_table_is_too_big() {
+    let rrr = RRRBitVec::new(random_bitvec(100), 21, 4);
+    let mut value = to_value(&rrr);
+    value["decode_table"] = Value::Bool(true);
+
+    let error = from_value(value).unwrap_err();
+    assert!(error.to_string().contains("decode table"), "{error}");
+}
+
+#[test]
+fn load_fails_when_parts_do_not_match() {
+    let rrr = RRRBitVec::new(random_bitvec(1000), 15, 4);
+    let other = to_value(&RRRBitVec::new(random_bitvec(1000), 15, 4));
+
+    for field in ["classes", "offsets", "rank_samples", "offset_samples"] {
+        let mut value = to_value(&rrr);
+        value[field] = other[field].clone();
+        if value == to_value(&rrr) {
+            continue;
+        }
+        assert!(from_value(value).is_err(), "{field}");
+    }
+}
+
+#[test]
+fn load_fails_when_class_is_greater_than_b() {
+    let mut classes: CompactIntVec<u64> = CompactIntVec::with_width(4);
+    classes.push(5u64);
+    let rrr = RRRBitVec::new(BitVec::from_value(false, 4), 4, 1);
+    let mut value = to_value(&rrr);
+    value["classes"] = serde_json::to_value(&classes).unwrap();
+
+    let error = from_value(value).unwrap_err();
+    assert!(error.to_string().contains("class 5"), "{error}");
+}
+
+#[test]
+fn load_fails_when_offset_is_out_of_range() {
+    // 8 choose 4 = 70 offsets, which need 7 bits
+    let rrr = RRRBitVec::new("11110000".parse().unwrap(), 8, 1);
+    let mut offsets: BitVec<u64> = BitVec::default();
+    offsets.push_bits(70u64, 7);
+    let mut value = to_value(&rrr);
+    value["offsets"] = serde_json::to_value(&offsets).unwrap();
+
+    let error = from_value(value).unwrap_err();
+    assert!(error.to_string().contains("offset out of range"), "{error}");
+}
+
+#[test]
+fn load_fails_when_padding_has_ones() {
+    // The last block has 2 bits, so the block of class 4 has 1s in its padding
+    let rrr = RRRBitVec::new("111111".parse().unwrap(), 4, 1);
+    let mut value = to_value(&rrr);
+    let mut classes: CompactIntVec<u64> = CompactIntVec::with_width(3);
+    classes.extend([4u64, 4]);
+    value["classes"] = serde_json::to_value(&classes).unwrap();
+
+    let error = from_value(value).unwrap_err();
+    assert!(error.to_string().contains("padding"), "{error}");
+}
+
+#[test]
+fn load_fails_when_bitvec_is_inconsistent() {
+    let rrr = RRRBitVec::new(random_bitvec(1000), 15, 4);
+    let mut value = to_value(&rrr);
+    value["offsets"]["len"] = Value::from(100_000);
+
+    assert!(from_value(value).is_err());
+}
+
+#[test]
+fn load_fails_when_bitvec_has_ones_past_its_length() {
+    let bitvec = (0..1000).map(|i| i % 3 == 0).collect::<BitVec>();
+    let rrr = RRRBitVec::new(bitvec, 15, 4);
+    let mut value = to_value(&rrr);
+    assert_ne!(rrr.offsets().len() % 64, 0);
+    let words = value["offsets"]["raw_data"].as_array_mut().unwrap();
+    let last = words.last_mut().unwrap();
+    *last = Value::from(last.as_u64().unwrap() | 1 << 63);
+
+    let error = from_value(value).unwrap_err();
+    assert!(error.to_string().contains("past its length"), "{error}");
+}
+
+#[test]
+fn bits_are_stored_in_u64_words() {
+    let rrr = RRRBitVec::new(random_bitvec(1000), 15, 4);
+    let value = to_value(&rrr);
+
+    let words = value["offsets"]["raw_data"].as_array().unwrap();
+    assert_eq!(words.len(), rrr.offsets().len().div_ceil(64));
+    let offsets: BitVec<u64> = serde_json::from_value(value["offsets"].clone()).unwrap();
+    assert!(offsets.iter().eq(rrr.offsets().iter()));
+    let classes: CompactIntVec<u64> = serde_json::from_value(value["classes"].clone()).unwrap();
+    assert!(classes
+        .iter()
+        .map(|class| class as usize)
+        .eq(rrr.classes().iter()));
+}
+
+#[test]
+fn load_with_u64_words() {
+    // "0110" in blocks of 2 bits, written as on any target
+    let value = serde_json::json!({
+        "version": 1,
+        "b": 2,
+        "k": 1,
+        "len": 4,
+        "total_rank": 2,
+        "classes": {"raw_data": {"raw_data": [5], "len": 4}, "width": 2, "len": 2},
+        "offsets": {"raw_data": [2], "len": 2},
+        "rank_samples": {"raw_data": {"raw_data": [36], "len": 6}, "width": 2, "len": 3},
+        "offset_samples": {"raw_data": {"raw_data": [36], "len": 6}, "width": 2, "len": 3},
+        "decode_table": false,
+    });
+    let rrr = from_value(value).unwrap();
+
+    assert_eq!(rrr.to_bitvec(), "0110".parse().unwrap());
+}
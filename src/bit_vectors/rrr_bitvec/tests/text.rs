@@ -0,0 +1,109 @@
+use super::*;
+use crate::test_utils::random_bitvec;
+
+fn invalid_parameters(s: &str) -> bool {
+    matches!(
+        s.parse::<RRRBitVec>(),
+        Err(ParseRRRBitVecError::InvalidParameters(_))
+    )
+}
+
+#[test]
+fn format() {
+    let rrr = RRRBitVec::new(BitVec::from([0b10010110u8]), 3, 2);
+
+    assert_eq!(rrr.to_string(), "b=3, k=2: [0, 1, 1, 0, 1, 0, 0, 1]");
+    assert_eq!(format!("{rrr:b}"), "b=3, k=2: 01101001");
+}
+
+#[test]
+fn format_when_there_is_a_decode_table() {
+    let rrr = RRRBitVec::new_with_decode_table(BitVec::from([0b10010110u8]), 3, 2);
+
+    assert_eq!(
+        rrr.to_string(),
+        "b=3, k=2, decode_table: [0, 1, 1, 0, 1, 0, 0, 1]"
+    );
+    assert_eq!(format!("{rrr:b}"), "b=3, k=2, decode_table: 01101001");
+}
+
+#[test]
+fn format_writes_the_bits_as_bitvec() {
+    let bitvec = random_bitvec(1000);
+    for rrr in [
+        RRRBitVec::new(bitvec.clone(), 15, 32),
+        RRRBitVec::new(bitvec.clone(), 100, 2),
+    ] {
+        let parameters = format!("b={}, k={}: ", rrr.b(), rrr.k());
+        assert_eq!(rrr.to_string(), format!("{parameters}{bitvec}"));
+        assert_eq!(format!("{rrr:b}"), format!("{parameters}{bitvec:b}"));
+    }
+}
+
+#[test]
+fn parse() {
+    let rrr = "b=3, k=2: 0110_1001".parse::<RRRBitVec>().unwrap();
+
+    assert_eq!(rrr.len(), 8);
+    assert_eq!(rrr.rank(8), Some(4));
+    assert_eq!(rrr.select(3), Some(5));
+    assert_eq!(rrr.b(), 3);
+    assert_eq!(rrr.k(), 2);
+    assert!(!rrr.has_decode_table());
+}
+
+#[test]
+fn parse_without_parameters() {
+    let rrr = "0110_1001".parse::<RRRBitVec>().unwrap();
+
+    assert_eq!(rrr.to_bitvec(), "01101001".parse::<BitVec>().unwrap());
+    assert_eq!(rrr.b(), 15);
+    assert_eq!(rrr.k(), 32);
+}
+
+#[test]
+fn parse_when_bits_are_invalid() {
+    let Err(ParseRRRBitVecError::InvalidBits(err)) = "b=3, k=2: 01x0".parse::<RRRBitVec>() else {
+        panic!("expected invalid bits");
+    };
+    assert_eq!(err.index(), 12);
+    assert_eq!(err.found(), 'x');
+
+    let Err(ParseRRRBitVecError::InvalidBits(err)) = "01x0".parse::<RRRBitVec>() else {
+        panic!("expected invalid bits");
+    };
+    assert_eq!(err.index(), 2);
+}
+
+#[test]
+fn parse_when_parameters_are_invalid() {
+    assert!(invalid_parameters("b=3: 0110"));
+    assert!(invalid_parameters("k=3: 0110"));
+    assert!(invalid_parameters("b=3, k=2, b=4: 0110"));
+    assert!(invalid_parameters("b=3, k=2, c=4: 0110"));
+    assert!(invalid_parameters("b=3, k=2, table: 0110"));
+    assert!(invalid_parameters("b=x, k=2: 0110"));
+    assert!(invalid_parameters("b=0, k=2: 0110"));
+    assert!(invalid_parameters("b=129, k=2: 0110"));
+    assert!(invalid_parameters("b=3, k=0: 0110"));
+    assert!(invalid_parameters("b=21, k=2, decode_table: 0110"));
+}
+
+#[test]
+fn parse_is_the_inverse_of_format() {
+    let bitvec = random_bitvec(1000);
+    for rrr in [
+        RRRBitVec::new(bitvec.clone(), 15, 32),
+        RRRBitVec::new(bitvec.clone(), 100, 2),
+        RRRBitVec::new_with_decode_table(bitvec.clone(), 7, 3),
+    ] {
+        for s in [rrr.to_string(), format!("{rrr:b}")] {
+            let parsed = s.parse::<RRRBitVec>().unwrap();
+            assert_eq!(parsed.to_bitvec(), bitvec);
+            assert_eq!(parsed.b(), rrr.b());
+            assert_eq!(parsed.k(), rrr.k());
+            assert_eq!(parsed.has_decode_table(), rrr.has_decode_table());
+            assert_eq!(parsed.to_string(), rrr.to_string());
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Conversions between [RRRBitVec] and text.
+//!
+//! The text starts with the parameters, followed by the bits in either of the forms of
+//! BitVec, e.g. `b=15, k=32: 0110` or `b=7, k=3, decode_table: [0, 1, 1, 0]`. Parsing
+//! the text builds the structure back with the same parameters. A text without
+//! parameters, such as a plain bit string, is built with `b = 15` and `k = 32`.
+
+use std::error::Error;
+use std::fmt::{Binary, Display, Formatter};
+use std::str::FromStr;
+
+use super::{RRRBitVec, MAX_B, MAX_DECODE_TABLE_B};
+use crate::bit_vectors::{BitVec, ParseBitVecError};
+
+/// Parameters of a text without them.
+const DEFAULT_B: usize = 15;
+const DEFAULT_K: usize = 32;
+
+/// Error returned when parsing an [RRRBitVec] from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRRRBitVecError {
+    /// The parameters before the `:` are malformed, missing or out of range.
+    InvalidParameters(String),
+    /// The bits after the parameters are invalid. The index of the error is relative
+    /// to the whole string.
+    InvalidBits(ParseBitVecError),
+}
+
+impl Display for ParseRRRBitVecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParameters(reason) => write!(f, "invalid parameters: {reason}"),
+            Self::InvalidBits(err) => write!(f, "invalid bits: {err}"),
+        }
+    }
+}
+
+impl Error for ParseRRRBitVecError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidParameters(_) => None,
+            Self::InvalidBits(err) => Some(err),
+        }
+    }
+}
+
+impl RRRBitVec {
+    fn write_parameters(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "b={}, k={}", self.b, self.k)?;
+        if self.has_decode_table() {
+            write!(f, ", decode_table")?;
+        }
+        write!(f, ": ")
+    }
+}
+
+// The formats decompress the bits first, so every block is decoded once instead of once
+// per bit. The decompressed bits take far less space than the text they are written to.
+impl Display for RRRBitVec {
+    /// Writes the parameters, then the bits as a list, the same as the `{}` format of
+    /// BitVec.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_parameters(f)?;
+        Display::fmt(&self.to_bitvec(), f)
+    }
+}
+
+impl Binary for RRRBitVec {
+    /// Writes the parameters, then the bits packed in index order, the same as the `{:b}`
+    /// format of BitVec.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_parameters(f)?;
+        for bit in self.to_bitvec().iter() {
+            write!(f, "{}", if bit { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RRRBitVec {
+    type Err = ParseRRRBitVecError;
+
+    /// Parses the output of the `{}` and `{:b}` formats. The bits follow the same rules
+    /// as BitVec, and the parameters, if any, must include both `b` and `k`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (b, k, decode_table, bits_start) = match s.split_once(':') {
+            Some((parameters, _)) => {
+                let (b, k, decode_table) = parse_parameters(parameters)?;
+                (b, k, decode_table, parameters.len() + 1)
+            }
+            None => (DEFAULT_B, DEFAULT_K, false, 0),
+        };
+        let bitvec: BitVec = s[bits_start..].parse().map_err(|err: ParseBitVecError| {
+            ParseRRRBitVecError::InvalidBits(err.shifted(bits_start))
+        })?;
+
+        Ok(if decode_table {
+            Self::new_with_decode_table(bitvec, b, k)
+        } else {
+            Self::new(bitvec, b, k)
+        })
+    }
+}
+
+/// Parses a comma separated list of `b=<n>`, `k=<n>` and an optional `decode_table`,
+/// checking the values so that building the structure does not panic.
+fn parse_parameters(s: &str) -> Result<(usize, usize, bool), ParseRRRBitVecError> {
+    let invalid = ParseRRRBitVecError::InvalidParameters;
+    let (mut b, mut k, mut decode_table) = (None, None, false);
+
+    for item in s.split(',').map(str::trim) {
+        let (target, value) = match item.split_once('=') {
+            Some((name, value)) => match name.trim() {
+                "b" => (&mut b, value.trim()),
+                "k" => (&mut k, value.trim()),
+                name => return Err(invalid(format!("unknown parameter {name:?}"))),
+            },
+            None if item == "decode_table" => {
+                decode_table = true;
+                continue;
+            }
+            None => return Err(invalid(format!("unknown parameter {item:?}"))),
+        };
+        if target.is_some() {
+            return Err(invalid(format!("repeated parameter in {item:?}")));
+        }
+        let value = value
+            .parse()
+            .map_err(|_| invalid(format!("invalid value in {item:?}")))?;
+        *target = Some(value);
+    }
+
+    let b = b.ok_or_else(|| invalid("missing b".to_string()))?;
+    let k = k.ok_or_else(|| invalid("missing k".to_string()))?;
+    if b == 0 || b > MAX_B {
+        return Err(invalid(format!("b must be between 1 and {MAX_B}, got {b}")));
+    }
+    if k == 0 {
+        return Err(invalid("k must be greater than 0".to_string()));
+    }
+    if decode_table && b > MAX_DECODE_TABLE_B {
+        return Err(invalid(format!(
+            "a decode table requires b to be at most {MAX_DECODE_TABLE_B}, got {b}"
+        )));
+    }
+    Ok((b, k, decode_table))
+}
@@ -1,13 +1,18 @@
 use std::marker::PhantomData;
 
-use crate::getbits;
 use crate::int_vectors::CompactIntVec;
 use crate::profiling::HeapSize;
+use crate::util::getbits;
 
 use super::rank_select::{Rank, Select};
-use super::BitVec;
+use super::{Access, BitVec};
 use crate::Build;
 
+/// Sparse bit vector represented with the Elias-Fano encoding of the positions of its 1s.
+/// The position of every 1 is split into its `r` lower bits, stored in a CompactIntVec, and
+/// its higher bits, stored in unary in a bit vector where the `j`-th 1 is at position
+/// `(position >> r) + j`. The higher bits are built into the structure `T`, which must
+/// support select so that the positions can be decoded.
 #[derive(Debug)]
 pub struct SDVec<T>
 where
@@ -15,6 +20,7 @@ where
 {
     r: usize,
     m: usize,
+    len: usize,
     select_structure: T,
     lower_bits: CompactIntVec,
 }
@@ -23,42 +29,134 @@ impl<T> SDVec<T>
 where
     T: Select,
 {
+    /// Builds the structure from the 1s of `data`, which are found by scanning its words.
     pub fn new<B: Build<BitVec, T>>(data: BitVec, spec: &B) -> Self {
-        let m = data.rank(data.len()).unwrap();
-        let n = data.len();
+        let m = data.count_ones(0..data.len());
+        Self::from_sorted_positions(data.iter_ones(), m, data.len(), spec)
+    }
+
+    /// Builds the structure in a single pass over the `m` positions of the 1s of a bit
+    /// vector of length `universe`, without materializing the bit vector. The number of
+    /// 1s sizes the structure up front, so the positions can come from any iterator.
+    ///
+    /// # Panics
+    /// If the positions are not strictly increasing, are not smaller than `universe`, or
+    /// there are not exactly `m` of them.
+    pub fn from_sorted_positions<I, B>(positions: I, m: usize, universe: usize, spec: &B) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+        B: Build<BitVec, T>,
+    {
+        let n = universe;
 
-        let r = (n / m).ilog2();
+        // If there are no 1s, use the parameters of a bit vector with a single 1,
+        // so the higher bits take only a couple of bits.
+        let r = (n / m.max(1)).checked_ilog2().unwrap_or(0);
 
         let mut higher_bits = BitVec::from_value(false, m + (n >> r));
 
         let r = r as usize;
         let mut lower_bits = CompactIntVec::with_capacity(r, m);
 
-        let mut j = 0;
+        let mut previous = None;
+        for (j, i) in positions.into_iter().enumerate() {
+            assert!(
+                previous.is_none_or(|previous| previous < i),
+                "The positions must be strictly increasing, got {i} after {}",
+                previous.unwrap_or_default()
+            );
+            assert!(
+                i < n,
+                "Position {i} is out of bounds of a universe of size {n}"
+            );
+            assert!(j < m, "There are more than {m} positions");
+            previous = Some(i);
 
-        for i in 0..n {
-            // TODO: optimize this reading the words in raw_data, and shifting right. Stop reading at n-1 so
-            // we don't read past the end of the bitvec. the data.read in a loop is very slow.
-
-            if data.read(i) {
-                // push i mod 2^r to L
-                lower_bits.push(getbits!(i, r, 0));
-                // i >> r indicates how many blocks of size 2^r we have before. we add j
-                // because of the number of 1s set in the unary code.
-                higher_bits.set((i >> r) + j, true);
-                // sum j after, because we index bitvecs starting from 0
-                j += 1;
-            }
+            // push i mod 2^r to L
+            lower_bits.push(getbits!(i, r, 0));
+            // i >> r indicates how many blocks of size 2^r we have before. we add j
+            // because of the number of 1s set in the unary code.
+            higher_bits.set((i >> r) + j, true);
         }
+        assert_eq!(lower_bits.len(), m, "There are fewer than {m} positions");
 
         let select_structure = spec.build(higher_bits);
         Self {
             r,
             m,
+            len: n,
             select_structure,
             lower_bits,
         }
     }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of 1s in the bit vector.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.m
+    }
+
+    #[inline]
+    pub fn lower_bits(&self) -> &CompactIntVec {
+        &self.lower_bits
+    }
+
+    #[inline]
+    pub fn select_structure(&self) -> &T {
+        &self.select_structure
+    }
+}
+
+impl<T> SDVec<T>
+where
+    T: Select + Access,
+{
+    /// Returns the number of 1s before the bucket of `index` and the position in the
+    /// higher bits where the 1s of the bucket start.
+    #[inline]
+    fn bucket_start(&self, index: usize) -> (usize, usize) {
+        let bucket = index >> self.r;
+        if bucket == 0 {
+            return (0, 0);
+        }
+        // The 1s of the bucket are right after the bucket-th 0 of the higher bits
+        let start = self.select_structure.select0(bucket).unwrap();
+        (start - bucket, start)
+    }
+}
+
+impl<T> Rank for SDVec<T>
+where
+    T: Select + Access,
+{
+    fn rank(&self, index: usize) -> Option<usize> {
+        if index > self.len {
+            return None;
+        }
+
+        let (mut rank, mut pos) = self.bucket_start(index);
+        let lower = getbits!(index, self.r, 0);
+
+        // The lower bits of the 1s in a bucket are sorted, so count the ones smaller than
+        // the lower bits of index.
+        while self.select_structure.access(pos) == Some(true)
+            && unsafe { self.lower_bits.get_unchecked(rank) } < lower
+        {
+            rank += 1;
+            pos += 1;
+        }
+        Some(rank)
+    }
 }
 
 impl<T> Select for SDVec<T>
@@ -83,8 +181,55 @@ where
         Some((higher | lower) + 1)
     }
 
-    fn select0(&self, _rank0: usize) -> Option<usize> {
-        todo!()
+    /// The 0s are not encoded, so this performs a binary search over the 1s, which takes
+    /// O(log m) selects.
+    fn select0(&self, rank0: usize) -> Option<usize> {
+        if rank0 == 0 {
+            return Some(0);
+        }
+
+        if rank0 > self.len - self.m {
+            return None;
+        }
+
+        // Find the number of 1s before the rank0-th 0, which is the greatest j such that there
+        // are less than rank0 0s before the j-th 1. There are select(j) - j 0s before the j-th 1.
+        let mut left = 0;
+        let mut right = self.m;
+        while left < right {
+            let mid = (left + right).div_ceil(2);
+            if self.select(mid).unwrap() - mid < rank0 {
+                left = mid;
+            } else {
+                right = mid - 1;
+            }
+        }
+
+        Some(rank0 + left)
+    }
+}
+
+impl<T> Access for SDVec<T>
+where
+    T: Select + Access,
+{
+    fn access(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+
+        let (mut rank, mut pos) = self.bucket_start(index);
+        let lower = getbits!(index, self.r, 0);
+
+        while self.select_structure.access(pos) == Some(true) {
+            let current = unsafe { self.lower_bits.get_unchecked(rank) };
+            if current >= lower {
+                return Some(current == lower);
+            }
+            rank += 1;
+            pos += 1;
+        }
+        Some(false)
     }
 }
 
@@ -139,55 +284,4 @@ where
 }
 
 #[cfg(test)]
-mod tests {
-    use crate::bit_vectors::rank_select::sparse_sampling_rank::SparseSamplingRankSpec;
-    use crate::bit_vectors::rank_select::RankStructure;
-    use crate::util::BitsRequired;
-
-    use super::*;
-    #[test]
-    fn new() {
-        let spec = super::super::rank_select::SparseSamplingRank::spec(4);
-        let bv = BitVec::from([
-            0b00101010u8,
-            0b10001010,
-            0b00000100,
-            0b00000000,
-            0b01001100,
-            0b00001011,
-            0b01000100,
-            0b00010000,
-        ]);
-        let sd_vec = SDVec::new(bv, &spec);
-
-        println!("M: {}", sd_vec.m);
-        println!("R: {}", sd_vec.r);
-        println!("L: {}", sd_vec.lower_bits);
-        println!("H: {}", sd_vec.select_structure.data());
-
-        dbg!(sd_vec.select(0));
-        dbg!(sd_vec.select(1).unwrap() + 1);
-        dbg!(sd_vec.select(2).unwrap() + 1);
-        dbg!(sd_vec.select(3).unwrap() + 1);
-        dbg!(sd_vec.select(4).unwrap() + 1);
-        dbg!(sd_vec.select(5).unwrap() + 1);
-        dbg!(sd_vec.select(6).unwrap() + 1);
-        dbg!(sd_vec.select(7).unwrap() + 1);
-        dbg!(sd_vec.select(8).unwrap() + 1);
-        dbg!(sd_vec.select(9).unwrap() + 1);
-        dbg!(sd_vec.select(10).unwrap() + 1);
-        dbg!(sd_vec.select(11).unwrap() + 1);
-        dbg!(sd_vec.select(12).unwrap() + 1);
-        dbg!(sd_vec.select(13).unwrap() + 1);
-        dbg!(sd_vec.select(14).unwrap() + 1);
-        dbg!(sd_vec.select(15).unwrap() + 1);
-        dbg!(sd_vec.select(16).unwrap() + 1);
-        dbg!(sd_vec.select(17));
-    }
-    use crate::bit_vectors::rank_select::tests_utils::test_select_for;
-    use crate::bit_vectors::rank_select::SparseSamplingRank;
-    test_select_for!(
-        SDVec<RankStructure<BitVec, SparseSamplingRank>>,
-        SparseSamplingRank::spec(4)
-    );
-}
+mod tests;
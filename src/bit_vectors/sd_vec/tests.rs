@@ -0,0 +1,191 @@
+use super::*;
+use crate::bit_vectors::rank_select::tests_utils::test_rank_select_access_for;
+use crate::bit_vectors::rank_select::{DenseSamplingRank, RankStructure, SparseSamplingRank};
+
+const TEST_DATA: [u8; 8] = [
+    0b00101010, 0b10001010, 0b00000100, 0b00000000, 0b01001100, 0b00001011, 0b01000100, 0b00010000,
+];
+
+#[test]
+fn new() {
+    let bv = BitVec::from(TEST_DATA);
+    let sd_vec = SDVec::new(bv, &SparseSamplingRank::spec(4));
+
+    // 16 ones in 64 bits, so the lower bits are log2(64 / 16) = 2 bits wide
+    assert_eq!(sd_vec.len(), 64);
+    assert_eq!(sd_vec.count_ones(), 16);
+    assert_eq!(sd_vec.lower_bits().width(), 2);
+    assert_eq!(sd_vec.lower_bits().len(), 16);
+    assert_eq!(sd_vec.select_structure().data().len(), 16 + 64 / 4);
+}
+
+#[test]
+fn new_when_all_zeros() {
+    let bv: BitVec = BitVec::from_value(false, 1000);
+    let sd_vec = SDVec::new(bv, &SparseSamplingRank::spec(4));
+
+    assert_eq!(sd_vec.count_ones(), 0);
+    assert!(sd_vec.lower_bits().is_empty());
+    // The higher bits do not grow with the length of the bit vector
+    assert_eq!(sd_vec.select_structure().data().len(), 1);
+}
+
+#[test]
+fn from_sorted_positions() {
+    let bv = BitVec::from(TEST_DATA);
+    let positions = bv.iter_ones().collect::<Vec<_>>();
+    let sd_vec = SDVec::from_sorted_positions(
+        positions.iter().copied(),
+        positions.len(),
+        bv.len(),
+        &SparseSamplingRank::spec(4),
+    );
+    let expected = SDVec::new(bv.clone(), &SparseSamplingRank::spec(4));
+
+    assert_eq!(sd_vec.len(), expected.len());
+    assert_eq!(sd_vec.count_ones(), expected.count_ones());
+    assert_eq!(
+        sd_vec.lower_bits().iter().collect::<Vec<_>>(),
+        expected.lower_bits().iter().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        sd_vec.select_structure().data(),
+        expected.select_structure().data()
+    );
+}
+
+#[test]
+fn from_sorted_positions_when_empty() {
+    let sd_vec = SDVec::from_sorted_positions([], 0, 100, &SparseSamplingRank::spec(4));
+
+    assert_eq!(sd_vec.len(), 100);
+    assert_eq!(sd_vec.count_ones(), 0);
+    assert_eq!(sd_vec.rank(100), Some(0));
+    assert_eq!(sd_vec.select(1), None);
+    assert_eq!(sd_vec.select0(100), Some(100));
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn from_sorted_positions_when_universe_is_huge() {
+    const UNIVERSE: usize = 1 << 40;
+    let positions = [0, 3, 1 << 20, (1 << 39) + 7, UNIVERSE - 1];
+    let sd_vec = SDVec::from_sorted_positions(
+        positions,
+        positions.len(),
+        UNIVERSE,
+        &DenseSamplingRank::spec(4),
+    );
+
+    // The higher bits take at most 3 bits per 1
+    assert!(sd_vec.select_structure().data().len() <= 3 * positions.len());
+    for (j, position) in positions.iter().enumerate() {
+        assert_eq!(sd_vec.select(j + 1), Some(position + 1));
+        assert_eq!(sd_vec.rank(*position), Some(j));
+        assert_eq!(sd_vec.access(*position), Some(true));
+        assert_eq!(
+            sd_vec.access(position.saturating_sub(1).max(1)),
+            Some(false)
+        );
+    }
+    assert_eq!(sd_vec.rank(UNIVERSE), Some(positions.len()));
+    assert_eq!(sd_vec.select0(4), Some(6));
+}
+
+#[test]
+#[should_panic]
+fn from_sorted_positions_fails_when_not_increasing() {
+    SDVec::from_sorted_positions([1, 5, 5, 7], 4, 10, &SparseSamplingRank::spec(4));
+}
+
+#[test]
+#[should_panic]
+fn from_sorted_positions_fails_when_out_of_bounds() {
+    SDVec::from_sorted_positions([1, 5, 10], 3, 10, &SparseSamplingRank::spec(4));
+}
+
+#[test]
+fn from_sorted_positions_when_iterator_has_no_length() {
+    let sd_vec = SDVec::from_sorted_positions(
+        (0..100).filter(|i| i % 7 == 3),
+        14,
+        100,
+        &SparseSamplingRank::spec(4),
+    );
+
+    assert_eq!(sd_vec.count_ones(), 14);
+    assert_eq!(sd_vec.select(1), Some(4));
+    assert_eq!(sd_vec.select(14), Some(95));
+}
+
+#[test]
+#[should_panic]
+fn from_sorted_positions_fails_when_there_are_more_positions() {
+    SDVec::from_sorted_positions([1, 5, 7], 2, 10, &SparseSamplingRank::spec(4));
+}
+
+#[test]
+#[should_panic]
+fn from_sorted_positions_fails_when_there_are_fewer_positions() {
+    SDVec::from_sorted_positions([1, 5, 7], 4, 10, &SparseSamplingRank::spec(4));
+}
+
+#[test]
+fn operations_match_bitvec() {
+    let bv = BitVec::from(TEST_DATA);
+    let sd_vec = SDVec::new(bv.clone(), &SparseSamplingRank::spec(4));
+
+    for i in 0..=bv.len() {
+        assert_eq!(sd_vec.rank(i), bv.rank(i));
+        assert_eq!(sd_vec.rank0(i), bv.rank0(i));
+        assert_eq!(sd_vec.access(i), bv.access(i));
+        assert_eq!(sd_vec.select(i), bv.select(i));
+        assert_eq!(sd_vec.select0(i), bv.select0(i));
+    }
+}
+
+#[test]
+fn operations_match_bitvec_when_sparse() {
+    let bv = (0..10_000)
+        .map(|_| rand::random::<u8>() < 4)
+        .collect::<BitVec>();
+    let sd_vec = SDVec::new(bv.clone(), &DenseSamplingRank::spec(4));
+
+    for i in 0..=bv.len() {
+        assert_eq!(sd_vec.rank(i), bv.rank(i));
+        assert_eq!(sd_vec.access(i), bv.access(i));
+        assert_eq!(sd_vec.select0(i), bv.select0(i));
+    }
+    for i in 0..=sd_vec.count_ones() + 1 {
+        assert_eq!(sd_vec.select(i), bv.select(i));
+    }
+}
+
+#[test]
+fn heap_size_in_bits() {
+    let bv = BitVec::from(TEST_DATA);
+    let sd_vec = SDVec::new(bv, &SparseSamplingRank::spec(4));
+
+    assert_eq!(
+        sd_vec.heap_size_in_bits(),
+        sd_vec.select_structure().heap_size_in_bits() + sd_vec.lower_bits().heap_size_in_bits()
+    );
+}
+
+mod when_higher_bits_use_sparse_sampling_rank {
+    use super::*;
+
+    test_rank_select_access_for!(
+        SDVec<RankStructure<BitVec, SparseSamplingRank>>,
+        SparseSamplingRank::spec(4)
+    );
+}
+
+mod when_higher_bits_use_dense_sampling_rank {
+    use super::*;
+
+    test_rank_select_access_for!(
+        SDVec<RankStructure<BitVec, DenseSamplingRank>>,
+        DenseSamplingRank::spec(2)
+    );
+}
@@ -208,7 +208,6 @@ where
 {
     fn select(&self, char: char, rank: usize) -> Option<usize> {
         let node = &self.root;
-        let rank = rank;
         let interval_left = 0;
         let interval_right = self.alphabet.len() - 1;
         unsafe { self.select_inner(node, char, interval_left, interval_right, rank) }
@@ -8,6 +8,9 @@ use crate::bit_vectors::rank_select::dense_sampling_rank::{
 use crate::bit_vectors::rank_select::sparse_sampling_rank::{
     SparseSamplingRank, SparseSamplingRankSpec,
 };
+use crate::bit_vectors::rank_select::sparse_sampling_select::{
+    SparseSamplingSelect, SparseSamplingSelectSpec,
+};
 use crate::bit_vectors::rrr_bitvec::{RRRBitVec, RRRBitVecSpec};
 
 fn build_test_wt() -> WaveletTreeNode<BitVec> {
@@ -325,6 +328,9 @@ const SPARSE_SAMPLING_RANK_4_SPEC: SparseSamplingRankSpec = SparseSamplingRank::
 const SPARSE_SAMPLING_RANK_8_SPEC: SparseSamplingRankSpec = SparseSamplingRank::spec(8);
 const SPARSE_SAMPLING_RANK_16_SPEC: SparseSamplingRankSpec = SparseSamplingRank::spec(16);
 const SPARSE_SAMPLING_RANK_20_SPEC: SparseSamplingRankSpec = SparseSamplingRank::spec(20);
+const SPARSE_SAMPLING_SELECT_8_4_SPEC: SparseSamplingSelectSpec = SparseSamplingSelect::spec(8, 4);
+const SPARSE_SAMPLING_SELECT_64_2_SPEC: SparseSamplingSelectSpec =
+    SparseSamplingSelect::spec(64, 2);
 const DENSE_SAMPLING_RANK_4_SPEC: DenseSamplingRankSpec = DenseSamplingRank::spec(4);
 const DENSE_SAMPLING_RANK_8_SPEC: DenseSamplingRankSpec = DenseSamplingRank::spec(8);
 const DENSE_SAMPLING_RANK_16_SPEC: DenseSamplingRankSpec = DenseSamplingRank::spec(16);
@@ -340,6 +346,8 @@ test_for_spec!(
     SPARSE_SAMPLING_RANK_8_SPEC,
     SPARSE_SAMPLING_RANK_16_SPEC,
     SPARSE_SAMPLING_RANK_20_SPEC,
+    SPARSE_SAMPLING_SELECT_8_4_SPEC,
+    SPARSE_SAMPLING_SELECT_64_2_SPEC,
     DENSE_SAMPLING_RANK_4_SPEC,
     DENSE_SAMPLING_RANK_8_SPEC,
     DENSE_SAMPLING_RANK_16_SPEC,
@@ -1,49 +1,63 @@
-use crate::util::BitsRequired;
+use crate::util::{BitsRequired, Word};
 
 use super::CompactIntVec;
 
-impl CompactIntVec {
-    pub fn iter(&self) -> Iter {
+impl<W: Word> CompactIntVec<W> {
+    pub fn iter(&self) -> Iter<'_, W> {
         Iter::new(self)
     }
+
+    /// Creates a CompactIntVec with the width required by the greatest value of the slice.
+    fn from_slice<T>(slice: &[T]) -> Self
+    where
+        T: BitsRequired + Copy + Into<W> + Ord,
+    {
+        let max_value = slice.iter().max().unwrap();
+        let bitwidth = max_value.bits_required() as usize;
+        let mut compact_int_vec = CompactIntVec::with_width(bitwidth);
+        for value in slice {
+            compact_int_vec.push(*value);
+        }
+        compact_int_vec
+    }
 }
-impl IntoIterator for CompactIntVec {
-    type Item = usize;
-    type IntoIter = IntoIter;
+impl<W: Word> IntoIterator for CompactIntVec<W> {
+    type Item = W;
+    type IntoIter = IntoIter<W>;
 
     fn into_iter(self) -> Self::IntoIter {
         IntoIter::new(self)
     }
 }
 
-impl<'a> IntoIterator for &'a CompactIntVec {
-    type Item = usize;
-    type IntoIter = Iter<'a>;
+impl<'a, W: Word> IntoIterator for &'a CompactIntVec<W> {
+    type Item = W;
+    type IntoIter = Iter<'a, W>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
-pub struct IntoIter {
-    compact_int_vec: CompactIntVec,
+pub struct IntoIter<W: Word = usize> {
+    compact_int_vec: CompactIntVec<W>,
     index: usize,
 }
 
-impl IntoIter {
-    pub fn new(compact_int_vec: CompactIntVec) -> Self {
+impl<W: Word> IntoIter<W> {
+    pub fn new(compact_int_vec: CompactIntVec<W>) -> Self {
         Self {
             compact_int_vec,
             index: 0,
         }
     }
 }
-pub struct Iter<'a> {
-    compact_int_vec: &'a CompactIntVec,
+pub struct Iter<'a, W: Word = usize> {
+    compact_int_vec: &'a CompactIntVec<W>,
     index: usize,
 }
 
-impl<'a> Iter<'a> {
-    pub fn new(compact_int_vec: &'a CompactIntVec) -> Self {
+impl<'a, W: Word> Iter<'a, W> {
+    pub fn new(compact_int_vec: &'a CompactIntVec<W>) -> Self {
         Self {
             compact_int_vec,
             index: 0,
@@ -51,8 +65,8 @@ impl<'a> Iter<'a> {
     }
 }
 
-impl Iterator for IntoIter {
-    type Item = usize;
+impl<W: Word> Iterator for IntoIter<W> {
+    type Item = W;
 
     fn next(&mut self) -> Option<Self::Item> {
         let value = self.compact_int_vec.get(self.index);
@@ -66,8 +80,8 @@ impl Iterator for IntoIter {
     }
 }
 
-impl Iterator for Iter<'_> {
-    type Item = usize;
+impl<W: Word> Iterator for Iter<'_, W> {
+    type Item = W;
 
     fn next(&mut self) -> Option<Self::Item> {
         let value = self.compact_int_vec.get(self.index);
@@ -81,8 +95,6 @@ impl Iterator for Iter<'_> {
     }
 }
 
-/// Trait to calculate the number of bits required to represent a value
-
 impl<T> From<&[T]> for CompactIntVec
 where
     T: BitsRequired + Copy + Into<usize> + Ord,
@@ -93,13 +105,7 @@ where
             "Cannot create a CompactIntVec from an empty slice, width cannot be inferred"
         );
 
-        let max_value = slice.iter().max().unwrap();
-        let bitwidth = max_value.bits_required() as usize;
-        let mut compact_int_vec = CompactIntVec::new(bitwidth);
-        for value in slice {
-            compact_int_vec.push(*value);
-        }
-        compact_int_vec
+        Self::from_slice(slice)
     }
 }
 
@@ -149,9 +155,10 @@ where
 
 /// CompactIntVec's int width is inferred from the iterator.
 /// This is a costly transformation, since it needs to collect all the elements into a Vec to calculate the bitwidth
-impl<T> FromIterator<T> for CompactIntVec
+impl<T, W> FromIterator<T> for CompactIntVec<W>
 where
-    T: BitsRequired + Copy + Into<usize> + Ord,
+    W: Word,
+    T: BitsRequired + Copy + Into<W> + Ord,
 {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         // TODO: avoid collecting into a Vec, and calculate the bitwidth from the iterator??
@@ -160,13 +167,14 @@ where
             !items.is_empty(),
             "Cannot create a CompactIntVec from an empty iterator, width cannot be inferred"
         );
-        Self::from(&items[..])
+        Self::from_slice(&items)
     }
 }
 
-impl<T> Extend<T> for CompactIntVec
+impl<T, W> Extend<T> for CompactIntVec<W>
 where
-    T: Into<usize>,
+    W: Word,
+    T: Into<W>,
 {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         for value in iter {
@@ -1,73 +1,95 @@
-use std::fmt::Display;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::num::ParseIntError;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{bit_vectors::BitVec, profiling::HeapSize};
+use crate::{bit_vectors::BitVec, profiling::HeapSize, util::Word};
 
 /// Data structure that stores a sequence of integers of a fixed width in a compact way.
 /// The underlying data structure is a bit vector, which bits are interpreted together to store integers.
 /// The width of the integers is specified when creating the data structure and cannot be modified.
-/// The width must be at most the width of usize, since it is required for the BitVec data structure.
+/// The width must be at most the width of the word type `W` of the underlying BitVec, which
+/// also is the type of the integers stored.
 
 // TODO: implement pretty to string
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CompactIntVec {
-    raw_data: BitVec,
+// The Word trait already requires the word to be (de)serializable
+#[serde(bound = "")]
+pub struct CompactIntVec<W: Word = usize> {
+    raw_data: BitVec<W>,
     width: usize,
     len: usize,
 }
 
 impl CompactIntVec {
+    #[inline]
+    pub fn new(width: usize) -> Self {
+        Self::with_width(width)
+    }
+
+    #[inline]
+    pub fn with_capacity(width: usize, capacity: usize) -> Self {
+        Self::with_width_and_capacity(width, capacity)
+    }
+}
+
+// As with BitVec, the constructors above are only for the default word, so they can be
+// called without naming it. These are their counterparts for any word.
+impl<W: Word> CompactIntVec<W> {
     // TODO: create a from raw parts method that allos us to interpretate a bit vector
     // as a vector of ints. This method should be called from_raw_parts,
     // or from_bitvec? we should only specify the width of the integers,
     // since len is calculated with ceil_div(bitvec.len(), width)
 
+    /// Creates an empty vector of integers of `width` bits.
     #[inline]
-    pub fn new(width: usize) -> Self {
+    pub fn with_width(width: usize) -> Self {
         Self::validate_width(width);
         Self {
-            raw_data: BitVec::new(),
+            raw_data: BitVec::default(),
             width,
             len: 0,
         }
     }
 
+    /// Creates an empty vector of integers of `width` bits with room for `capacity` of them.
     #[inline]
-    pub fn from_raw_parts(mut raw_data: BitVec, width: usize) -> Self {
+    pub fn with_width_and_capacity(width: usize, capacity: usize) -> Self {
         Self::validate_width(width);
 
-        let leftover_bits = raw_data.len() % width;
-        if leftover_bits != 0 {
-            // fill the last integer with zeros in order to have a complete integer
-            raw_data.push_bits(0usize, width - (leftover_bits));
-        }
-        let len = raw_data.len() / width;
-
         Self {
-            raw_data,
+            raw_data: BitVec::with_bit_capacity(width * capacity),
             width,
-            len,
+            len: 0,
         }
     }
 
     #[inline]
-    pub fn with_capacity(width: usize, capacity: usize) -> Self {
+    pub fn from_raw_parts(mut raw_data: BitVec<W>, width: usize) -> Self {
         Self::validate_width(width);
 
+        let leftover_bits = raw_data.len() % width;
+        if leftover_bits != 0 {
+            // fill the last integer with zeros in order to have a complete integer
+            raw_data.push_bits(W::ZERO, width - (leftover_bits));
+        }
+        let len = raw_data.len() / width;
+
         Self {
-            raw_data: BitVec::with_capacity(width * capacity),
+            raw_data,
             width,
-            len: 0,
+            len,
         }
     }
 
     #[inline]
     fn validate_width(width: usize) {
         assert!(
-            width <= BitVec::CONTAINER_WIDTH,
+            width <= W::BITS,
             "The width of the integers must be at most {} bits, got {width} bits",
-            BitVec::CONTAINER_WIDTH
+            W::BITS
         );
     }
 
@@ -81,7 +103,7 @@ impl CompactIntVec {
     }
 
     #[inline]
-    pub fn raw_data(&self) -> &BitVec {
+    pub fn raw_data(&self) -> &BitVec<W> {
         &self.raw_data
     }
 
@@ -93,11 +115,11 @@ impl CompactIntVec {
     #[inline]
     pub fn push<T>(&mut self, value: T)
     where
-        T: Into<usize>,
+        T: Into<W>,
     {
-        let value: usize = value.into();
+        let value: W = value.into();
         assert!(
-            value.checked_shr(self.width as u32).unwrap_or(0) == 0,
+            value.checked_shr(self.width as u32).unwrap_or(W::ZERO) == W::ZERO,
             "Value {value} does not fit within {} bits",
             self.width
         );
@@ -107,7 +129,7 @@ impl CompactIntVec {
     }
 
     #[inline]
-    pub fn pop(&mut self) -> Option<usize> {
+    pub fn pop(&mut self) -> Option<W> {
         if self.is_empty() {
             return None;
         }
@@ -117,7 +139,7 @@ impl CompactIntVec {
 
     /// When width==0, get of any index will return 0
     #[inline]
-    pub fn get(&self, index: usize) -> Option<usize> {
+    pub fn get(&self, index: usize) -> Option<W> {
         if index >= self.len() {
             return None;
         }
@@ -127,7 +149,7 @@ impl CompactIntVec {
     /// # Safety
     /// Calling this method with an out-of-bounds index is undefined behavior.
     #[inline]
-    pub unsafe fn get_unchecked(&self, index: usize) -> usize {
+    pub unsafe fn get_unchecked(&self, index: usize) -> W {
         self.raw_data
             .read_bits_unchecked(index * self.width, self.width)
     }
@@ -135,11 +157,11 @@ impl CompactIntVec {
     #[inline]
     pub fn set<T>(&mut self, index: usize, value: T)
     where
-        T: Into<usize>,
+        T: Into<W>,
     {
-        let value: usize = value.into();
+        let value: W = value.into();
         assert!(
-            value.checked_shr(self.width as u32).unwrap_or(0) == 0,
+            value.checked_shr(self.width as u32).unwrap_or(W::ZERO) == W::ZERO,
             "Value {value} does not fit within {} bits",
             self.width
         );
@@ -158,13 +180,13 @@ impl CompactIntVec {
     }
 }
 
-impl HeapSize for CompactIntVec {
+impl<W: Word> HeapSize for CompactIntVec<W> {
     fn heap_size_in_bits(&self) -> usize {
         self.raw_data.heap_size_in_bits()
     }
 }
 
-impl Display for CompactIntVec {
+impl<W: Word> Display for CompactIntVec<W> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut iter = self.iter();
         write!(f, "[")?;
@@ -178,6 +200,78 @@ impl Display for CompactIntVec {
     }
 }
 
+/// Error returned when parsing a CompactIntVec from a string with a value that is
+/// not an integer that fits within the word type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCompactIntVecError {
+    index: usize,
+    source: ParseIntError,
+}
+
+impl ParseCompactIntVecError {
+    /// Index of the value that could not be parsed.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Display for ParseCompactIntVecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid value at index {}: {}", self.index, self.source)
+    }
+}
+
+impl Error for ParseCompactIntVecError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl<W: Word> FromStr for CompactIntVec<W> {
+    type Err = ParseCompactIntVecError;
+
+    /// Parses a list of integers separated by `,`, optionally enclosed in brackets,
+    /// as written by `Display`. The width is the minimum width needed to store all
+    /// the values, so it may be smaller than the width of the vector that was formatted:
+    /// the width is not part of the text, and only the values round-trip. To keep a
+    /// given width, create the vector with [CompactIntVec::new] and extend it with the
+    /// parsed values.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let content = trimmed
+            .strip_prefix('[')
+            .and_then(|inner| inner.strip_suffix(']'))
+            .unwrap_or(trimmed);
+
+        let values = if content.trim().is_empty() {
+            Vec::new()
+        } else {
+            content
+                .split(',')
+                .enumerate()
+                .map(|(index, value)| {
+                    value
+                        .trim()
+                        .parse::<W>()
+                        .map_err(|source| ParseCompactIntVecError { index, source })
+                })
+                .collect::<Result<Vec<W>, _>>()?
+        };
+
+        let width = values
+            .iter()
+            .map(|value| W::BITS - value.leading_zeros() as usize)
+            .max()
+            .unwrap_or(0);
+        let mut vec = Self::with_width_and_capacity(width, values.len());
+        for value in values {
+            vec.push(value);
+        }
+        Ok(vec)
+    }
+}
+
 pub mod iter;
 
 #[cfg(test)]
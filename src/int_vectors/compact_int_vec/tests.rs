@@ -349,3 +349,81 @@ test_for_width!(17, 18, 20, 25, 31, 32);
 test_for_width!(33, 40, 48, 63);
 
 test_from_for_type!(u8, u16, usize);
+
+#[test]
+fn push_and_get_when_word_is_u128() {
+    let values = (0..1000)
+        .map(|_| rand::random::<u128>() >> 28)
+        .collect::<Vec<_>>();
+    let mut compact_int_vec = CompactIntVec::<u128>::with_width(100);
+    for value in values.iter() {
+        compact_int_vec.push(*value);
+    }
+
+    assert_eq!(compact_int_vec.len(), values.len());
+    assert_eq!(compact_int_vec.iter().collect::<Vec<_>>(), values);
+    assert_eq!(compact_int_vec.pop(), values.last().copied());
+}
+
+#[test]
+fn collect_when_word_is_u8() {
+    let compact_int_vec = [3u8, 1, 7, 0, 5].into_iter().collect::<CompactIntVec<u8>>();
+
+    assert_eq!(compact_int_vec.width(), 3);
+    assert_eq!(compact_int_vec.raw_data().raw_data().len(), 2);
+    assert_eq!(compact_int_vec.iter().collect::<Vec<_>>(), [3, 1, 7, 0, 5]);
+}
+
+#[test]
+fn parse() {
+    let compact_int_vec = "[3, 1, 7, 0, 5]".parse::<CompactIntVec>().unwrap();
+
+    assert_eq!(compact_int_vec.width(), 3);
+    assert_eq!(compact_int_vec.iter().collect::<Vec<_>>(), [3, 1, 7, 0, 5]);
+    assert_eq!(
+        "3,1 , 7,0,5"
+            .parse::<CompactIntVec>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>(),
+        [3, 1, 7, 0, 5]
+    );
+}
+
+#[test]
+fn parse_when_empty() {
+    for s in ["", "[]", " [ ] "] {
+        let compact_int_vec = s.parse::<CompactIntVec>().unwrap();
+        assert!(compact_int_vec.is_empty());
+        assert_eq!(compact_int_vec.width(), 0);
+    }
+}
+
+#[test]
+fn parse_fails_when_value_is_invalid() {
+    let error = "[3, 1, x, 0]".parse::<CompactIntVec>().unwrap_err();
+    assert_eq!(error.index(), 2);
+    assert_eq!(
+        error.to_string(),
+        "invalid value at index 2: invalid digit found in string"
+    );
+
+    // The values must fit within the word type
+    let error = "[3, 256]".parse::<CompactIntVec<u8>>().unwrap_err();
+    assert_eq!(error.index(), 1);
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn parse_is_the_inverse_of_format() {
+    let values = (0..500)
+        .map(|_| rand::random::<u64>() >> 20)
+        .collect::<Vec<_>>();
+    let compact_int_vec = values.iter().copied().collect::<CompactIntVec<u64>>();
+    let parsed = compact_int_vec
+        .to_string()
+        .parse::<CompactIntVec<u64>>()
+        .unwrap();
+
+    assert_eq!(parsed.iter().collect::<Vec<_>>(), values);
+}
@@ -1,5 +1,5 @@
 pub mod compact_int_vec;
 pub mod variable_size_int_vec;
 
-pub use compact_int_vec::CompactIntVec;
+pub use compact_int_vec::{CompactIntVec, ParseCompactIntVecError};
 pub use variable_size_int_vec::VariableSizeIntVec;
@@ -1,7 +1,7 @@
 use super::VariableSizeIntVec;
 
 impl VariableSizeIntVec<'_> {
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_, '_> {
         Iter::new(self)
     }
 }
@@ -62,7 +62,7 @@ impl<'a> VariableSizeIntVec<'a> {
         );
 
         // Store the prev_sizes up to the current element
-        if self.len() % self.k == 0 {
+        if self.len().is_multiple_of(self.k) {
             self.samples.push(self.raw_data.len());
         }
 
@@ -81,7 +81,7 @@ impl<'a> VariableSizeIntVec<'a> {
 
         let size = (self.size_function)(index);
 
-        if index % self.k == 0 {
+        if index.is_multiple_of(self.k) {
             self.samples.pop();
         }
 
@@ -59,7 +59,7 @@ fn push_when_sizes_are_not_sorted() {
 
     assert_eq!(int_vec.len(), 9);
     assert_eq!(int_vec.raw_data().len(), 16);
-    assert_eq!(*int_vec.samples.get(0).unwrap(), 0);
+    assert_eq!(*int_vec.samples.first().unwrap(), 0);
     assert_eq!(*int_vec.samples.get(1).unwrap(), 10);
     assert_eq!(*int_vec.samples.get(2).unwrap(), 16);
 
@@ -82,7 +82,7 @@ fn pop() {
 
     assert_eq!(int_vec.len(), 5);
     assert_eq!(int_vec.raw_data().len(), 10);
-    assert_eq!(*int_vec.samples.get(0).unwrap(), 0);
+    assert_eq!(*int_vec.samples.first().unwrap(), 0);
     assert_eq!(*int_vec.samples.get(1).unwrap(), 6);
 
     assert_eq!(int_vec.pop(), Some(10));
@@ -111,7 +111,7 @@ fn set() {
 
     assert_eq!(int_vec.len(), 5);
     assert_eq!(int_vec.raw_data().len(), 10);
-    assert_eq!(*int_vec.samples.get(0).unwrap(), 0);
+    assert_eq!(*int_vec.samples.first().unwrap(), 0);
     assert_eq!(*int_vec.samples.get(1).unwrap(), 6);
 
     int_vec.set(0, 0usize);
@@ -121,7 +121,7 @@ fn set() {
     int_vec.set(4, 0b0110usize);
 
     assert_eq!(int_vec.len(), 5);
-    assert_eq!(*int_vec.samples.get(0).unwrap(), 0);
+    assert_eq!(*int_vec.samples.first().unwrap(), 0);
     assert_eq!(*int_vec.samples.get(1).unwrap(), 6);
 
     assert_eq!(int_vec.get(0), 0);
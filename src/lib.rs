@@ -4,6 +4,9 @@ pub mod int_vectors;
 pub mod profiling;
 pub mod util;
 
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 pub trait Build<T, O> {
     fn build(&self, data: T) -> O;
 }
@@ -0,0 +1,11 @@
+//! Helpers shared by the tests of the different modules.
+
+use crate::bit_vectors::BitVec;
+
+pub(crate) fn random_bools(len: usize) -> Vec<bool> {
+    (0..len).map(|_| rand::random::<bool>()).collect()
+}
+
+pub(crate) fn random_bitvec(len: usize) -> BitVec {
+    (0..len).map(|_| rand::random::<bool>()).collect()
+}
@@ -1,32 +1,51 @@
-// Bitfield  macros
+use std::fmt::{Binary, Debug, Display, LowerHex};
+use std::hash::Hash;
+use std::num::ParseIntError;
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr,
+    ShrAssign,
+};
+use std::str::FromStr;
 
-/// The mask to extract $len bits at bit offset $off.
-// generates a bitmask of max len 64
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
-pub(crate) const USIZE_BITS: usize = std::mem::size_of::<usize>() * 8;
+// Bitfield  macros
 
-/// The mask to extract $len bits at bit offset $off. Only valid for usize values.
+/// The mask to extract $len bits at bit offset $off. The mask is of type $t,
+/// or usize if omitted.
 macro_rules! bitmask {
     ( $len:expr, $off:expr ) => {
-        usize::MAX
-            .checked_shr((crate::util::USIZE_BITS - $len) as u32)
-            .unwrap_or(0)
+        $crate::util::bitmask!(usize, $len, $off)
+    };
+    ( $t:ty, $len:expr, $off:expr ) => {
+        <$t as $crate::util::Word>::MAX
+            .checked_shr((<$t as $crate::util::Word>::BITS - $len) as u32)
+            .unwrap_or(<$t as $crate::util::Word>::ZERO)
             << $off
     };
 }
 
-/// Extract $len bits from $val at bit offset $off. Only valid for usize values.
+/// Extract $len bits from $val at bit offset $off. $val must be of type $t,
+/// or usize if omitted.
 macro_rules! getbits {
     ( $val: expr, $len:expr, $off:expr ) => {
-        ($val & $crate::util::bitmask!($len, $off)) >> $off
+        $crate::util::getbits!(usize, $val, $len, $off)
+    };
+    ( $t:ty, $val: expr, $len:expr, $off:expr ) => {
+        ($val & $crate::util::bitmask!($t, $len, $off)) >> $off
     };
 }
 
-/// Update $len bits in $var at bit offset $off to $val. Only valid for usize values.
+/// Update $len bits in $var at bit offset $off to $val. $var and $val must be of type $t,
+/// or usize if omitted.
 macro_rules! setbits {
     ( $var: expr, $len:expr, $off:expr, $val: expr ) => {
-        $var = ($var & !$crate::util::bitmask!($len, $off))
-            | (($val << $off) & $crate::util::bitmask!($len, $off))
+        $crate::util::setbits!(usize, $var, $len, $off, $val)
+    };
+    ( $t:ty, $var: expr, $len:expr, $off:expr, $val: expr ) => {
+        $var = ($var & !$crate::util::bitmask!($t, $len, $off))
+            | (($val << $off) & $crate::util::bitmask!($t, $len, $off))
     };
 }
 
@@ -52,9 +71,245 @@ macro_rules! impl_bits_required_for {
 impl_bits_required_for!(u8, u16, usize, u32, u64, u128);
 
 pub(crate) fn ceil_div(a: usize, b: usize) -> usize {
-    (a + b - 1) / b
+    a.div_ceil(b)
+}
+
+/// Unsigned integer type that can be used as the underlying storage of bit vectors.
+/// The methods mirror the ones of the primitive types, so generic code reads
+/// the same as code written for a concrete type.
+pub trait Word:
+    Copy
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + Binary
+    + LowerHex
+    + Send
+    + Sync
+    + Not<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + BitAndAssign
+    + BitOrAssign
+    + BitXorAssign
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+    + ShlAssign<usize>
+    + ShrAssign<usize>
+    + From<bool>
+    + From<u8>
+    + FromStr<Err = ParseIntError>
+    + Serialize
+    + DeserializeOwned
+    + 'static
+{
+    /// Number of bits of the type.
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+
+    fn count_ones(self) -> u32;
+    fn count_zeros(self) -> u32;
+    fn leading_zeros(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+    fn checked_shl(self, rhs: u32) -> Option<Self>;
+    fn checked_shr(self, rhs: u32) -> Option<Self>;
+    fn reverse_bits(self) -> Self;
+
+    /// Returns the lowest 64 bits of the value.
+    fn as_u64(self) -> u64;
+}
+
+macro_rules! impl_word_for {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MAX: Self = <$t>::MAX;
+
+                #[inline]
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                #[inline]
+                fn count_zeros(self) -> u32 {
+                    <$t>::count_zeros(self)
+                }
+
+                #[inline]
+                fn leading_zeros(self) -> u32 {
+                    <$t>::leading_zeros(self)
+                }
+
+                #[inline]
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+
+                #[inline]
+                fn checked_shl(self, rhs: u32) -> Option<Self> {
+                    <$t>::checked_shl(self, rhs)
+                }
+
+                #[inline]
+                fn checked_shr(self, rhs: u32) -> Option<Self> {
+                    <$t>::checked_shr(self, rhs)
+                }
+
+                #[inline]
+                fn reverse_bits(self) -> Self {
+                    <$t>::reverse_bits(self)
+                }
+
+                #[inline]
+                fn as_u64(self) -> u64 {
+                    self as u64
+                }
+            }
+        )*
+    };
+}
+impl_word_for!(u8, u16, u32, u64, u128, usize);
+
+/// Returns the position of the `k`-th (starting from 0) set bit of `word`,
+/// counting from the least significant bit.
+/// The result is undefined if `word` has `k` or less set bits.
+#[inline]
+pub fn select_in_word<W: Word>(word: W, k: usize) -> usize {
+    debug_assert!(
+        k < word.count_ones() as usize,
+        "Cannot select the {k}-th set bit of a word with {} set bits",
+        word.count_ones()
+    );
+    if W::BITS <= 64 {
+        return select_in_u64(word.as_u64(), k as u64) as usize;
+    }
+
+    // Words wider than 64 bits are split into their lower and higher 64 bits
+    let low = word.as_u64();
+    let low_ones = low.count_ones() as usize;
+    if k < low_ones {
+        select_in_u64(low, k as u64) as usize
+    } else {
+        64 + select_in_u64((word >> 64).as_u64(), (k - low_ones) as u64) as usize
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+#[inline]
+fn select_in_u64(word: u64, k: u64) -> u64 {
+    // Deposit a single bit at the position of the k-th set bit of the word
+    unsafe { std::arch::x86_64::_pdep_u64(1 << k, word).trailing_zeros() as u64 }
+}
+
+/// Broadword select, see "Broadword Implementation of Rank/Select Queries" (Vigna, 2008).
+/// It finds the byte that contains the k-th set bit with byte-wise popcounts, and then
+/// selects inside that byte with a lookup table.
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+#[inline]
+fn select_in_u64(word: u64, k: u64) -> u64 {
+    const ONES_STEP_4: u64 = 0x1111_1111_1111_1111;
+    const ONES_STEP_8: u64 = 0x0101_0101_0101_0101;
+    const MSBS_STEP_8: u64 = 0x80 * ONES_STEP_8;
+
+    // Popcount of each byte
+    let mut byte_counts = word - ((word & (0xA * ONES_STEP_4)) >> 1);
+    byte_counts = (byte_counts & (0x3 * ONES_STEP_4)) + ((byte_counts >> 2) & (0x3 * ONES_STEP_4));
+    byte_counts = (byte_counts + (byte_counts >> 4)) & (0xF * ONES_STEP_8);
+
+    // Each byte holds the number of set bits up to that byte (included)
+    let byte_sums = byte_counts.wrapping_mul(ONES_STEP_8);
+
+    // The MSB of each byte is set if k >= the number of set bits up to that byte,
+    // so the number of MSBs set is the index of the byte that contains the k-th set bit
+    let k_step_8 = k * ONES_STEP_8;
+    let geq_k_step_8 = ((k_step_8 | MSBS_STEP_8) - byte_sums) & MSBS_STEP_8;
+    let place = geq_k_step_8.count_ones() as u64 * 8;
+    let byte_rank = k - (((byte_sums << 8) >> place) & 0xFF);
+
+    let byte = (word >> place) & 0xFF;
+    place + SELECT_IN_BYTE[(byte | (byte_rank << 8)) as usize] as u64
+}
+
+/// `SELECT_IN_BYTE[byte | (k << 8)]` is the position of the `k`-th set bit of `byte`.
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+static SELECT_IN_BYTE: [u8; 256 * 8] = get_select_in_byte_table();
+
+// Precompute select in byte table at compile time
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+const fn get_select_in_byte_table() -> [u8; 256 * 8] {
+    let mut table = [8; 256 * 8];
+    let mut byte = 0;
+    while byte < 256 {
+        let mut k = 0;
+        let mut i = 0;
+        while i < 8 {
+            if (byte >> i) & 1 == 1 {
+                table[byte | (k << 8)] = i as u8;
+                k += 1;
+            }
+            i += 1;
+        }
+        byte += 1;
+    }
+    table
 }
 
 pub(crate) use bitmask;
 pub(crate) use getbits;
 pub(crate) use setbits;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_select_in_word(word: usize, k: usize) -> usize {
+        (0..usize::BITS as usize)
+            .filter(|i| (word >> i) & 1 == 1)
+            .nth(k)
+            .unwrap()
+    }
+
+    #[test]
+    fn select_in_word_when_all_ones() {
+        for k in 0..usize::BITS as usize {
+            assert_eq!(select_in_word(usize::MAX, k), k);
+        }
+    }
+
+    #[test]
+    fn select_in_word_with_single_bit() {
+        for i in 0..usize::BITS as usize {
+            assert_eq!(select_in_word(1usize << i, 0), i);
+        }
+    }
+
+    #[test]
+    fn select_in_word_with_u128_values() {
+        for _ in 0..1000 {
+            let word = rand::random::<u128>();
+            let naive = (0..u128::BITS as usize).filter(|i| (word >> i) & 1 == 1);
+            for (k, i) in naive.enumerate() {
+                assert_eq!(select_in_word(word, k), i);
+            }
+        }
+    }
+
+    #[test]
+    fn select_in_word_with_random_values() {
+        for _ in 0..1000 {
+            let word = rand::random::<usize>();
+            for k in 0..word.count_ones() as usize {
+                assert_eq!(select_in_word(word, k), naive_select_in_word(word, k));
+            }
+        }
+    }
+}